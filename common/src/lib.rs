@@ -1,21 +1,37 @@
+pub mod random;
+
 use std::fs;
+use std::path::{Path, PathBuf};
 
 fn generate_path_from_day(day: u8) -> String {
     format!("day-{:02}/src/day-{:02}-input.txt", day, day)
 }
 
-pub fn read_from_file(path_from_workspace_root: &str) -> String {
-    match fs::read_to_string(&path_from_workspace_root) {
+fn resolve_from_workspace_root(path_from_workspace_root: &str) -> PathBuf {
+    let common_crate_root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    match common_crate_root.parent() {
+        Some(workspace_root) => workspace_root.join(path_from_workspace_root),
+        None => PathBuf::from(path_from_workspace_root),
+    }
+}
+
+pub fn read_from_file<P: AsRef<Path>>(path: P) -> String {
+    match fs::read_to_string(&path) {
         Ok(result) => result,
         Err(error) => panic!(
             "Could not read from local file `{}`\n  Message: \"{:?}\"",
-            &path_from_workspace_root, &error
+            path.as_ref().display(),
+            &error
         ),
     }
 }
 
+// the inputs are checked in next to each day's sources, so they are found
+// from the workspace root. `cargo test` runs in the package directory and
+// `cargo run` wherever it is called, so the current directory is no help
 pub fn read_input(day: u8) -> String {
     let path_from_workspace_root = generate_path_from_day(day);
 
-    read_from_file(&path_from_workspace_root)
+    read_from_file(resolve_from_workspace_root(&path_from_workspace_root))
 }
@@ -0,0 +1,47 @@
+// a small xorshift generator, so that anything drawn at random from a seed
+// is the same on every run and every machine
+pub struct Xorshift(u64);
+
+impl Xorshift {
+    pub fn new(seed: u64) -> Xorshift {
+        // xorshift never leaves a zero state, so the seed is mixed first
+        Xorshift((seed ^ 0x2545_F491_4F6C_DD1D).max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+
+        self.0
+    }
+
+    // a number in `0..=maximum`
+    pub fn up_to(&mut self, maximum: usize) -> usize {
+        (self.next_u64() % (maximum as u64 + 1)) as usize
+    }
+
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.next_u64() % 100 < percent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xorshift_test() {
+        let mut first = Xorshift::new(0);
+        let mut second = Xorshift::new(0);
+
+        for _ in 0..100 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+
+        assert_ne!(Xorshift::new(1).next_u64(), Xorshift::new(2).next_u64());
+        assert!((0..1000).all(|_| first.up_to(6) <= 6));
+        assert!(!(0..1000).any(|_| first.chance(0)));
+        assert!((0..1000).all(|_| first.chance(100)));
+    }
+}
@@ -0,0 +1,137 @@
+/*
+    Prints a calibration document with known answers, for teaching and for
+    test data:
+    > generate <lines> <part 1 total> <part 2 total> [options]
+
+    Options are `--seed=N` (0 by default), `--noise=N` for up to N noise
+    letters around each digit (3 by default), `--middle=N` for up to N extra
+    digits between the first and the last one (2 by default) and
+    `--overlaps` to also write spelled digits sharing letters, like "twone".
+
+    The same arguments always print the same document, and both totals are
+    checked with the part 1 and part 2 solvers before anything is printed.
+*/
+
+use std::env;
+use std::process;
+
+use day_01_lib::generator::{generate_document, GeneratorSettings};
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse::<T>()
+        .map_err(|_| format!("Expected a number for {name}, got `{value}`"))
+}
+
+fn parse_settings(arguments: &[String]) -> Result<GeneratorSettings, String> {
+    let (positional, options): (Vec<&String>, Vec<&String>) = arguments
+        .iter()
+        .partition(|argument| !argument.starts_with("--"));
+
+    let [line_count, part_1_total, part_2_total] = positional[..] else {
+        return Err(String::from(
+            "Expected `<lines> <part 1 total> <part 2 total> [options]`",
+        ));
+    };
+
+    let mut settings = GeneratorSettings {
+        line_count: parse_number("the line count", line_count)?,
+        part_1_total: parse_number("the part 1 total", part_1_total)?,
+        part_2_total: parse_number("the part 2 total", part_2_total)?,
+        max_noise: 3,
+        max_middle_digits: 2,
+        tricky_overlaps: false,
+        seed: 0,
+    };
+
+    for option in options {
+        match option.split_once('=') {
+            Some(("--seed", value)) => settings.seed = parse_number("--seed", value)?,
+            Some(("--noise", value)) => settings.max_noise = parse_number("--noise", value)?,
+            Some(("--middle", value)) => {
+                settings.max_middle_digits = parse_number("--middle", value)?
+            }
+            None if option == "--overlaps" => settings.tricky_overlaps = true,
+            _ => return Err(format!("Unknown option `{option}`")),
+        }
+    }
+
+    Ok(settings)
+}
+
+fn main() {
+    let arguments: Vec<String> = env::args().skip(1).collect();
+    let result = parse_settings(&arguments)
+        .and_then(|settings| generate_document(&settings).map_err(|error| error.to_string()));
+
+    match result {
+        Ok(document) => print!("{}", document),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arguments(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_settings_test() {
+        assert_eq!(
+            parse_settings(&arguments("7 142 281")),
+            Ok(GeneratorSettings {
+                line_count: 7,
+                part_1_total: 142,
+                part_2_total: 281,
+                max_noise: 3,
+                max_middle_digits: 2,
+                tricky_overlaps: false,
+                seed: 0,
+            })
+        );
+        assert_eq!(
+            parse_settings(&arguments(
+                "--overlaps 1000 54990 --seed=42 54473 --noise=5"
+            )),
+            Ok(GeneratorSettings {
+                line_count: 1000,
+                part_1_total: 54990,
+                part_2_total: 54473,
+                max_noise: 5,
+                max_middle_digits: 2,
+                tricky_overlaps: true,
+                seed: 42,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_settings_error_test() {
+        assert_eq!(
+            parse_settings(&arguments("7 142 281 --colour")),
+            Err(String::from("Unknown option `--colour`"))
+        );
+        assert_eq!(
+            parse_settings(&arguments("3 abc 50")),
+            Err(String::from(
+                "Expected a number for the part 1 total, got `abc`"
+            ))
+        );
+        assert_eq!(
+            parse_settings(&arguments("7 142 281 --seed=x")),
+            Err(String::from("Expected a number for --seed, got `x`"))
+        );
+        assert_eq!(
+            parse_settings(&arguments("7 142")),
+            Err(String::from(
+                "Expected `<lines> <part 1 total> <part 2 total> [options]`"
+            ))
+        );
+    }
+}
@@ -0,0 +1,74 @@
+/*
+    Lists the lines of the day 01 input whose part 2 calibration value
+    depends on whether spelled digits may share letters.
+
+    With shared letters, "eightwo" ends in 2, as the puzzle intends. If each
+    spelled digit instead consumes its letters from left to right, "eight"
+    uses up the "t" and the line ends in 8.
+*/
+
+use common::read_input;
+use day_01_lib::vocabulary::{Language, Vocabulary};
+use day_01_lib::{parse_calibration_document, OverlapDifference};
+
+fn format_value(value: Option<u64>) -> String {
+    value.map_or(String::from("-"), |value| value.to_string())
+}
+
+fn format_difference(difference: &OverlapDifference) -> String {
+    format!(
+        "line {:>4}: {:>3} shared letters, {:>3} consuming  {}",
+        difference.line_number,
+        format_value(difference.shared_letters_value),
+        format_value(difference.consuming_value),
+        difference.content
+    )
+}
+
+fn main() {
+    let file = read_input(1);
+    let document = parse_calibration_document(&file);
+    let vocabulary = Vocabulary::new(Language::English);
+
+    let differences = document.overlap_differences(&vocabulary);
+
+    for difference in differences.iter() {
+        println!("{}", format_difference(difference));
+    }
+
+    let shared_letters_sum: u64 = differences
+        .iter()
+        .filter_map(|difference| difference.shared_letters_value)
+        .sum();
+    let consuming_sum: u64 = differences
+        .iter()
+        .filter_map(|difference| difference.consuming_value)
+        .sum();
+
+    println!(
+        "{} of {} lines differ, changing the total by {}",
+        differences.len(),
+        document.lines.len(),
+        consuming_sum as i64 - shared_letters_sum as i64
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_difference_test() {
+        let difference = OverlapDifference {
+            line_number: 12,
+            content: "xtwone",
+            shared_letters_value: Some(21),
+            consuming_value: Some(22),
+        };
+
+        assert_eq!(
+            format_difference(&difference),
+            "line   12:  21 shared letters,  22 consuming  xtwone"
+        );
+    }
+}
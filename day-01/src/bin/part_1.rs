@@ -23,41 +23,40 @@
     calibration values?
 */
 
-use regex::Regex;
+use std::env;
+use std::process;
 
 use common::read_input;
-use day_01_lib::combine_first_and_last_number;
+use day_01_lib::{parse_calibration_document, CalibrationError, DigitRules, Mode, Selection};
 
-fn get_first_and_last_number(string: &str) -> (&str, &str) {
-    let first_and_last_number_regex =
-        Regex::new(r"^\D*(?<first_number>\d)(?:.*(?<second_number>\d)\D*$)?").unwrap();
-
-    let captures = first_and_last_number_regex.captures(string).unwrap();
-
-    let first_number = captures.get(1).map_or("0", |capture| capture.as_str());
-    let second_number = captures
-        .get(2)
-        .map_or(first_number, |capture| capture.as_str());
-
-    (first_number, second_number)
-}
-
-fn part_1() -> i32 {
+fn part_1(mode: Mode) -> Result<u64, CalibrationError> {
     let file = read_input(1);
+    let document = parse_calibration_document(&file);
 
-    let mut result = 0;
-
-    for line in file.split("\n") {
-        let (first_number, last_number) = get_first_and_last_number(line);
+    document.sum(DigitRules::NUMERIC, Selection::FIRST_AND_LAST, mode)
+}
 
-        result += combine_first_and_last_number(&first_number, &last_number);
+fn parse_arguments(arguments: &[String]) -> Result<Mode, String> {
+    match arguments {
+        [] => Ok(Mode::Strict),
+        [flag] if flag == "--lenient" => Ok(Mode::Lenient),
+        [other, ..] => Err(format!("Unknown option `{other}`")),
     }
-
-    result
 }
 
+// a line without a digit is an error, unless `--lenient` is given to skip it
 fn main() {
-    println!("{}", part_1());
+    let arguments: Vec<String> = env::args().skip(1).collect();
+    let result = parse_arguments(&arguments)
+        .and_then(|mode| part_1(mode).map_err(|error| error.to_string()));
+
+    match result {
+        Ok(result) => println!("{}", result),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -65,15 +64,21 @@ mod tests {
     use super::*;
 
     #[test]
-    fn get_first_and_last_number_test() {
-        assert_eq!(get_first_and_last_number("1abc2"), ("1", "2"));
-        assert_eq!(get_first_and_last_number("pqr3stu8vwx"), ("3", "8"));
-        assert_eq!(get_first_and_last_number("a1b2c3d4e5f"), ("1", "5"));
-        assert_eq!(get_first_and_last_number("treb7uchet"), ("7", "7"));
+    fn part_1_test() {
+        assert_eq!(part_1(Mode::Strict), Ok(54990));
+        assert_eq!(part_1(Mode::Lenient), Ok(54990));
     }
 
     #[test]
-    fn part_1_test() {
-        assert_eq!(part_1(), 54990)
+    fn parse_arguments_test() {
+        assert_eq!(parse_arguments(&[]), Ok(Mode::Strict));
+        assert_eq!(
+            parse_arguments(&[String::from("--lenient")]),
+            Ok(Mode::Lenient)
+        );
+        assert_eq!(
+            parse_arguments(&[String::from("--strict")]),
+            Err(String::from("Unknown option `--strict`"))
+        );
     }
 }
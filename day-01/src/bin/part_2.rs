@@ -24,92 +24,128 @@
     What is the sum of all of the calibration values?
 */
 
-use regex::Regex;
+use std::env;
+use std::process;
 
 use common::read_input;
-use day_01_lib::combine_first_and_last_number;
-
-fn match_capture(string: &str) -> &str {
-    match string {
-        "one" => "1",
-        "two" => "2",
-        "three" => "3",
-        "four" => "4",
-        "five" => "5",
-        "six" => "6",
-        "seven" => "7",
-        "eight" => "8",
-        "nine" => "9",
-        rest => rest,
-    }
-}
-
-fn get_first_and_last_number(string: &str) -> (&str, &str) {
-    let first_and_last_number_regex = Regex::new(r"^.*?(?<first_number>\d|one|two|three|four|five|six|seven|eight|nine)(?:.*(?<second_number>\d|one|two|three|four|five|six|seven|eight|nine))?.*?$").unwrap();
-
-    let captures = first_and_last_number_regex.captures(string).unwrap();
-
-    let first_number = captures
-        .name("first_number")
-        .map_or("0", |capture| match_capture(capture.as_str()));
-
-    let second_number = captures
-        .name("second_number")
-        .map_or(first_number, |capture| match_capture(capture.as_str()));
-
-    (first_number, second_number)
+use day_01_lib::vocabulary::{read_vocabulary, Language, Vocabulary};
+use day_01_lib::{
+    parse_calibration_document, CalibrationError, DigitRules, Mode, OverlapPolicy, Selection,
+};
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    vocabulary: Vocabulary,
+    mode: Mode,
 }
 
-fn part_2() -> i32 {
+fn part_2(vocabulary: &Vocabulary, mode: Mode) -> Result<u64, CalibrationError> {
     let file = read_input(1);
+    let document = parse_calibration_document(&file);
 
-    let mut result = 0;
-
-    for line in file.split("\n") {
-        let (first_number, last_number) = get_first_and_last_number(line);
+    document.sum(
+        DigitRules::numeric_and_spelled(vocabulary, OverlapPolicy::SharedLetters),
+        Selection::FIRST_AND_LAST,
+        mode,
+    )
+}
 
-        result += combine_first_and_last_number(&first_number, &last_number);
+fn parse_arguments(arguments: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        vocabulary: Vocabulary::new(Language::English),
+        mode: Mode::Strict,
+    };
+    let mut arguments = arguments.iter();
+
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--vocabulary" => {
+                let path = arguments
+                    .next()
+                    .ok_or_else(|| format!("Expected a value after `{argument}`"))?;
+
+                options.vocabulary = read_vocabulary(path).map_err(|error| error.to_string())?;
+            }
+            "--lenient" => options.mode = Mode::Lenient,
+            other => return Err(format!("Unknown option `{other}`")),
+        }
     }
 
-    result
+    Ok(options)
 }
 
+// the spelled digits are English unless `--vocabulary <file>` gives a file
+// with one `word=value` pair per line. A line without a digit is an error,
+// unless `--lenient` is given to skip it
 fn main() {
-    println!("{}", &part_2());
+    let arguments: Vec<String> = env::args().skip(1).collect();
+    let result = parse_arguments(&arguments).and_then(|options| {
+        part_2(&options.vocabulary, options.mode).map_err(|error| error.to_string())
+    });
+
+    match result {
+        Ok(result) => println!("{}", result),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
-    #[test]
-    fn match_capture_test() {
-        assert_eq!(match_capture("one"), "1");
-        assert_eq!(match_capture("two"), "2");
-        assert_eq!(match_capture("three"), "3");
-        assert_eq!(match_capture("four"), "4");
-        assert_eq!(match_capture("five"), "5");
-        assert_eq!(match_capture("six"), "6");
-        assert_eq!(match_capture("seven"), "7");
-        assert_eq!(match_capture("eight"), "8");
-        assert_eq!(match_capture("nine"), "9");
-        assert_eq!(match_capture("1"), "1");
-        assert_eq!(match_capture("4"), "4");
-        assert_eq!(match_capture("5"), "5");
-        assert_eq!(match_capture("9"), "9");
+    fn arguments(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
     }
 
     #[test]
-    fn get_first_and_last_number_test() {
-        assert_eq!(get_first_and_last_number("two1nine"), ("2", "9"));
-        assert_eq!(get_first_and_last_number("eightwothree"), ("8", "3"));
-        assert_eq!(get_first_and_last_number("abcone2threexyz"), ("1", "3"));
-        assert_eq!(get_first_and_last_number("7pqrstsixteen"), ("7", "6"));
-        assert_eq!(get_first_and_last_number("6zfxp"), ("6", "6"));
+    fn part_2_test() {
+        let english = Vocabulary::new(Language::English);
+
+        assert_eq!(part_2(&english, Mode::Strict), Ok(54473));
+        assert_eq!(part_2(&english, Mode::Lenient), Ok(54473));
     }
 
     #[test]
-    fn part_1_test() {
-        assert_eq!(part_2(), 54473)
+    fn parse_arguments_test() {
+        let path = env::temp_dir().join("day-01-part-2-vocabulary.txt");
+        fs::write(&path, "# numbers only\n").unwrap();
+
+        assert_eq!(
+            parse_arguments(&[]),
+            Ok(Options {
+                vocabulary: Vocabulary::new(Language::English),
+                mode: Mode::Strict,
+            })
+        );
+        assert_eq!(
+            parse_arguments(&arguments(&format!(
+                "--lenient --vocabulary {}",
+                path.display()
+            ))),
+            Ok(Options {
+                vocabulary: Vocabulary { words: Vec::new() },
+                mode: Mode::Lenient,
+            })
+        );
+
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            parse_arguments(&arguments("--vocabulary")),
+            Err(String::from("Expected a value after `--vocabulary`"))
+        );
+        assert!(
+            parse_arguments(&arguments("--vocabulary no-such-vocabulary.txt"))
+                .unwrap_err()
+                .starts_with("Could not read vocabulary from `no-such-vocabulary.txt`: ")
+        );
+        assert_eq!(
+            parse_arguments(&arguments("--language en")),
+            Err(String::from("Unknown option `--language`"))
+        );
     }
 }
@@ -0,0 +1,124 @@
+/*
+    Prints every line of the day 01 input with the first and last digit it
+    was given highlighted, followed by the calibration value and the running
+    total. Pass `1` or `2` to pick the part, part 2 being the default.
+
+    In a terminal the first digit is green, the last one cyan and letters
+    shared by both yellow. Otherwise the first digit is marked with `[]` and
+    the last with `{}`, so "eightwo" shows as "[eigh{t]wo}".
+*/
+
+use std::env;
+use std::io::{stdout, IsTerminal};
+use std::process;
+
+use common::read_input;
+use day_01_lib::highlight::{highlight_first_and_last, Style};
+use day_01_lib::vocabulary::{Language, Vocabulary};
+use day_01_lib::{
+    calibration_value, extract_tokens, parse_calibration_document, DigitRules, OverlapPolicy,
+    Selection,
+};
+
+fn render_line(line: &str, rules: DigitRules, style: Style) -> (String, Option<u64>) {
+    let Some(tokens) = extract_tokens(line, rules, Selection::FIRST_AND_LAST) else {
+        return (line.to_string(), None);
+    };
+
+    let highlighted = highlight_first_and_last(line, tokens[0], tokens[1], style);
+    let value = calibration_value(line, rules, Selection::FIRST_AND_LAST).ok();
+
+    (highlighted, value)
+}
+
+fn rules_for_part<'a>(part: &str, vocabulary: &'a Vocabulary) -> Result<DigitRules<'a>, String> {
+    match part {
+        "1" => Ok(DigitRules::NUMERIC),
+        "2" => Ok(DigitRules::numeric_and_spelled(
+            vocabulary,
+            OverlapPolicy::SharedLetters,
+        )),
+        other => Err(format!("Expected part `1` or `2`, got `{other}`")),
+    }
+}
+
+fn main() {
+    let part = env::args().nth(1).unwrap_or(String::from("2"));
+    let vocabulary = Vocabulary::new(Language::English);
+
+    let rules = match rules_for_part(&part, &vocabulary) {
+        Ok(rules) => rules,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    };
+
+    let style = if stdout().is_terminal() {
+        Style::Ansi
+    } else {
+        Style::Brackets
+    };
+
+    let file = read_input(1);
+    let document = parse_calibration_document(&file);
+
+    let mut total: u64 = 0;
+
+    for (index, line) in document.lines.iter().enumerate() {
+        let (highlighted, value) = render_line(line, rules, style);
+        total += value.unwrap_or(0);
+
+        let value = value.map_or(String::from("-"), |value| value.to_string());
+
+        println!(
+            "{:>4} {:>3} {:>6}  {}",
+            index + 1,
+            value,
+            total,
+            highlighted
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_line_test() {
+        let english = Vocabulary::new(Language::English);
+        let rules = DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters);
+
+        assert_eq!(
+            render_line("7pqrstsixteen", rules, Style::Brackets),
+            (String::from("[7]pqrst{six}teen"), Some(76))
+        );
+        assert_eq!(
+            render_line("zoneight234", rules, Style::Brackets),
+            (String::from("z[one]ight23{4}"), Some(14))
+        );
+        assert_eq!(
+            render_line("nothing", DigitRules::NUMERIC, Style::Brackets),
+            (String::from("nothing"), None)
+        );
+    }
+
+    #[test]
+    fn rules_for_part_test() {
+        let english = Vocabulary::new(Language::English);
+
+        assert_eq!(rules_for_part("1", &english), Ok(DigitRules::NUMERIC));
+        assert_eq!(
+            rules_for_part("2", &english),
+            Ok(DigitRules::numeric_and_spelled(
+                &english,
+                OverlapPolicy::SharedLetters
+            ))
+        );
+        assert_eq!(
+            rules_for_part("3", &english),
+            Err(String::from("Expected part `1` or `2`, got `3`"))
+        );
+    }
+}
@@ -0,0 +1,437 @@
+use std::error::Error;
+use std::fmt;
+
+use common::random::Xorshift;
+
+use crate::vocabulary::{Language, Vocabulary};
+use crate::{parse_calibration_document, DigitRules, Mode, OverlapPolicy, Selection};
+
+// none of these letters appear in an English spelled digit, so noise made
+// from them can never be read as one
+const NOISE_LETTERS: &[u8] = b"abcdjklmpqyz";
+
+const SPELLED_DIGITS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+// spelled digits sharing letters, with the value of the first and of the last
+const OVERLAPS: [(&str, u8, u8); 8] = [
+    ("oneight", 1, 8),
+    ("twone", 2, 1),
+    ("threeight", 3, 8),
+    ("fiveight", 5, 8),
+    ("sevenine", 7, 9),
+    ("eightwo", 8, 2),
+    ("eighthree", 8, 3),
+    ("nineight", 9, 8),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeneratorSettings {
+    pub line_count: usize,
+    pub part_1_total: u64,
+    pub part_2_total: u64,
+    // up to this many noise letters around and between the digits of a line
+    pub max_noise: usize,
+    // up to this many extra numeric or spelled digits between the first and
+    // the last one, which neither part should pick up
+    pub max_middle_digits: usize,
+    pub tricky_overlaps: bool,
+    pub seed: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GeneratorError {
+    UnreachableTotal {
+        part: u8,
+        total: u64,
+        line_count: usize,
+    },
+    VerificationFailed {
+        part: u8,
+        expected: u64,
+        actual: Option<u64>,
+    },
+}
+
+// `count` digits from `lowest..=9` adding up to `total`
+fn split_into_digits(total: u64, count: usize, lowest: u8, random: &mut Xorshift) -> Vec<u8> {
+    let mut digits = vec![lowest; count];
+    let mut remaining = total - lowest as u64 * count as u64;
+
+    while remaining > 0 {
+        let index = random.up_to(count - 1);
+
+        if digits[index] < 9 {
+            digits[index] += 1;
+            remaining -= 1;
+        }
+    }
+
+    digits
+}
+
+// the first and last digit of every line, each from `lowest..=9`, such that
+// the calibration values add up to `total`
+fn distribute_total(
+    part: u8,
+    total: u64,
+    line_count: usize,
+    lowest: u8,
+    random: &mut Xorshift,
+) -> Result<Vec<(u8, u8)>, GeneratorError> {
+    let lines = line_count as u64;
+    let digit_sums = lowest as u64 * lines..=9 * lines;
+
+    // the last digits add up to the units of the total plus some tens, and
+    // the first digits to whatever tens are left
+    let candidates: Vec<u64> = digit_sums
+        .clone()
+        .filter(|last_sum| last_sum % 10 == total % 10 && *last_sum <= total)
+        .filter(|last_sum| digit_sums.contains(&((total - last_sum) / 10)))
+        .collect();
+
+    if candidates.is_empty() {
+        return Err(GeneratorError::UnreachableTotal {
+            part,
+            total,
+            line_count,
+        });
+    }
+
+    let last_sum = candidates[random.up_to(candidates.len() - 1)];
+    let first_sum = (total - last_sum) / 10;
+
+    let first_digits = split_into_digits(first_sum, line_count, lowest, random);
+    let last_digits = split_into_digits(last_sum, line_count, lowest, random);
+
+    Ok(first_digits.into_iter().zip(last_digits).collect())
+}
+
+fn push_noise(line: &mut String, settings: &GeneratorSettings, random: &mut Xorshift) {
+    for _ in 0..random.up_to(settings.max_noise) {
+        line.push(char::from(
+            NOISE_LETTERS[random.up_to(NOISE_LETTERS.len() - 1)],
+        ));
+    }
+}
+
+fn push_digit(line: &mut String, digit: u8) {
+    line.push(char::from(b'0' + digit));
+}
+
+fn spelled_first(digit: u8, settings: &GeneratorSettings, random: &mut Xorshift) -> &'static str {
+    let overlaps: Vec<&str> = OVERLAPS
+        .iter()
+        .filter(|(_, first, _)| *first == digit)
+        .map(|(word, _, _)| *word)
+        .collect();
+
+    if settings.tricky_overlaps && !overlaps.is_empty() && random.chance(50) {
+        overlaps[random.up_to(overlaps.len() - 1)]
+    } else {
+        SPELLED_DIGITS[digit as usize - 1]
+    }
+}
+
+fn spelled_last(digit: u8, settings: &GeneratorSettings, random: &mut Xorshift) -> &'static str {
+    let overlaps: Vec<&str> = OVERLAPS
+        .iter()
+        .filter(|(_, _, last)| *last == digit)
+        .map(|(word, _, _)| *word)
+        .collect();
+
+    if settings.tricky_overlaps && !overlaps.is_empty() && random.chance(50) {
+        overlaps[random.up_to(overlaps.len() - 1)]
+    } else {
+        SPELLED_DIGITS[digit as usize - 1]
+    }
+}
+
+// the numeric digits give the part 1 value. A spelled digit goes before the
+// first numeric one when part 2 needs a different first digit, and after the
+// last numeric one when it needs a different last digit
+fn generate_line(
+    (first_numeric, last_numeric): (u8, u8),
+    (first, last): (u8, u8),
+    settings: &GeneratorSettings,
+    random: &mut Xorshift,
+) -> String {
+    let mut line = String::new();
+
+    push_noise(&mut line, settings, random);
+
+    if first != first_numeric || random.chance(25) {
+        line.push_str(spelled_first(first, settings, random));
+        push_noise(&mut line, settings, random);
+    }
+
+    push_digit(&mut line, first_numeric);
+
+    let single_digit = first_numeric == last_numeric && random.chance(25);
+
+    if !single_digit {
+        for _ in 0..random.up_to(settings.max_middle_digits) {
+            push_noise(&mut line, settings, random);
+
+            if random.chance(50) {
+                push_digit(&mut line, random.up_to(9) as u8);
+            } else if settings.tricky_overlaps && random.chance(50) {
+                line.push_str(OVERLAPS[random.up_to(OVERLAPS.len() - 1)].0);
+            } else {
+                line.push_str(SPELLED_DIGITS[random.up_to(8)]);
+            }
+        }
+
+        push_noise(&mut line, settings, random);
+        push_digit(&mut line, last_numeric);
+    }
+
+    if last != last_numeric || random.chance(25) {
+        push_noise(&mut line, settings, random);
+        line.push_str(spelled_last(last, settings, random));
+    }
+
+    push_noise(&mut line, settings, random);
+
+    line
+}
+
+fn verify(
+    part: u8,
+    document: &str,
+    rules: DigitRules,
+    expected: u64,
+) -> Result<(), GeneratorError> {
+    let actual = parse_calibration_document(document)
+        .sum::<u64>(rules, Selection::FIRST_AND_LAST, Mode::Strict)
+        .ok();
+
+    if actual == Some(expected) {
+        Ok(())
+    } else {
+        Err(GeneratorError::VerificationFailed {
+            part,
+            expected,
+            actual,
+        })
+    }
+}
+
+// part 1 digits may be 0, but spelled digits start at one, so the part 2
+// total needs at least 11 per line
+pub fn generate_document(settings: &GeneratorSettings) -> Result<String, GeneratorError> {
+    let mut random = Xorshift::new(settings.seed);
+
+    let numeric_digits = distribute_total(
+        1,
+        settings.part_1_total,
+        settings.line_count,
+        0,
+        &mut random,
+    )?;
+    let digits = distribute_total(
+        2,
+        settings.part_2_total,
+        settings.line_count,
+        1,
+        &mut random,
+    )?;
+
+    let mut document = String::new();
+
+    for (numeric_digits, digits) in numeric_digits.into_iter().zip(digits) {
+        document.push_str(&generate_line(
+            numeric_digits,
+            digits,
+            settings,
+            &mut random,
+        ));
+        document.push('\n');
+    }
+
+    let vocabulary = Vocabulary::new(Language::English);
+
+    verify(1, &document, DigitRules::NUMERIC, settings.part_1_total)?;
+    verify(
+        2,
+        &document,
+        DigitRules::numeric_and_spelled(&vocabulary, OverlapPolicy::SharedLetters),
+        settings.part_2_total,
+    )?;
+
+    Ok(document)
+}
+
+impl fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeneratorError::UnreachableTotal {
+                part,
+                total,
+                line_count,
+            } => write!(
+                f,
+                "A part {part} total of {total} cannot be reached with {line_count} line(s)"
+            ),
+            GeneratorError::VerificationFailed {
+                part,
+                expected,
+                actual,
+            } => match actual {
+                Some(actual) => write!(
+                    f,
+                    "Generated document has a part {part} total of {actual} instead of {expected}"
+                ),
+                None => write!(
+                    f,
+                    "Generated document could not be solved for part {part}, expected {expected}"
+                ),
+            },
+        }
+    }
+}
+
+impl Error for GeneratorError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calibration_value;
+
+    fn settings(line_count: usize, part_1_total: u64, part_2_total: u64) -> GeneratorSettings {
+        GeneratorSettings {
+            line_count,
+            part_1_total,
+            part_2_total,
+            max_noise: 4,
+            max_middle_digits: 3,
+            tricky_overlaps: true,
+            seed: 2023,
+        }
+    }
+
+    #[test]
+    fn split_into_digits_test() {
+        let mut random = Xorshift::new(1);
+
+        for (total, count, lowest) in [(0, 3, 0), (27, 3, 0), (14, 5, 1), (45, 5, 1), (30, 7, 2)] {
+            let digits = split_into_digits(total, count, lowest, &mut random);
+
+            assert_eq!(digits.len(), count);
+            assert_eq!(digits.iter().map(|digit| *digit as u64).sum::<u64>(), total);
+            assert!(digits.iter().all(|digit| (lowest..=9).contains(digit)));
+        }
+    }
+
+    #[test]
+    fn generate_document_test() {
+        for seed in 0..200 {
+            let settings = GeneratorSettings {
+                seed,
+                ..settings(1 + seed as usize % 20, 0, 0)
+            };
+            let lines = settings.line_count as u64;
+            let settings = GeneratorSettings {
+                part_1_total: seed * 7919 % (99 * lines + 1),
+                part_2_total: 11 * lines + seed * 104729 % (88 * lines + 1),
+                ..settings
+            };
+
+            match generate_document(&settings) {
+                Ok(document) => assert_eq!(document.lines().count(), settings.line_count),
+                Err(GeneratorError::UnreachableTotal { .. }) => {}
+                Err(error) => panic!("{error}"),
+            }
+        }
+    }
+
+    #[test]
+    fn generate_document_example_test() {
+        let document = generate_document(&settings(7, 142, 281)).unwrap();
+        let vocabulary = Vocabulary::new(Language::English);
+        let rules = DigitRules::numeric_and_spelled(&vocabulary, OverlapPolicy::SharedLetters);
+
+        let part_1: u64 = document
+            .lines()
+            .map(|line| {
+                calibration_value::<u64>(line, DigitRules::NUMERIC, Selection::FIRST_AND_LAST)
+                    .unwrap()
+            })
+            .sum();
+        let part_2: u64 = document
+            .lines()
+            .map(|line| calibration_value::<u64>(line, rules, Selection::FIRST_AND_LAST).unwrap())
+            .sum();
+
+        assert_eq!((part_1, part_2), (142, 281));
+        assert_eq!(document, generate_document(&settings(7, 142, 281)).unwrap());
+        assert_ne!(
+            document,
+            generate_document(&GeneratorSettings {
+                seed: 2024,
+                ..settings(7, 142, 281)
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn generate_document_overlaps_test() {
+        let with_overlaps = generate_document(&settings(50, 2500, 2500)).unwrap();
+        let without_overlaps = generate_document(&GeneratorSettings {
+            tricky_overlaps: false,
+            ..settings(50, 2500, 2500)
+        })
+        .unwrap();
+
+        let has_overlap =
+            |document: &str| OVERLAPS.iter().any(|(word, _, _)| document.contains(word));
+
+        assert!(has_overlap(&with_overlaps));
+        assert!(!has_overlap(&without_overlaps));
+    }
+
+    #[test]
+    fn generate_document_unreachable_test() {
+        assert_eq!(
+            generate_document(&settings(2, 199, 100)),
+            Err(GeneratorError::UnreachableTotal {
+                part: 1,
+                total: 199,
+                line_count: 2
+            })
+        );
+        assert_eq!(
+            generate_document(&settings(2, 100, 21)),
+            Err(GeneratorError::UnreachableTotal {
+                part: 2,
+                total: 21,
+                line_count: 2
+            })
+        );
+        assert_eq!(
+            generate_document(&settings(1, 50, 20)),
+            Err(GeneratorError::UnreachableTotal {
+                part: 2,
+                total: 20,
+                line_count: 1
+            })
+        );
+        assert_eq!(generate_document(&settings(0, 0, 0)), Ok(String::new()));
+    }
+
+    #[test]
+    fn generator_error_display_test() {
+        let error = GeneratorError::UnreachableTotal {
+            part: 2,
+            total: 20,
+            line_count: 1,
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "A part 2 total of 20 cannot be reached with 1 line(s)"
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use crate::Token;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Style {
+    Ansi,
+    Brackets,
+}
+
+const FIRST_COLOUR: &str = "\x1b[1;32m";
+const LAST_COLOUR: &str = "\x1b[1;36m";
+const SHARED_COLOUR: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
+
+fn ansi_colour(in_first: bool, in_last: bool) -> &'static str {
+    match (in_first, in_last) {
+        (true, true) => SHARED_COLOUR,
+        (true, false) => FIRST_COLOUR,
+        (false, true) => LAST_COLOUR,
+        (false, false) => RESET,
+    }
+}
+
+// green for the first token, cyan for the last one and yellow for letters
+// the two share, like the "t" in "eightwo"
+fn highlight_ansi(line: &str, first: Token, last: Token) -> String {
+    let mut result = String::new();
+    let mut current_colour = RESET;
+
+    for (index, character) in line.char_indices() {
+        let in_first = (first.start..first.end).contains(&index);
+        let in_last = (last.start..last.end).contains(&index);
+        let colour = ansi_colour(in_first, in_last);
+
+        if colour != current_colour {
+            result.push_str(colour);
+            current_colour = colour;
+        }
+
+        result.push(character);
+    }
+
+    if current_colour != RESET {
+        result.push_str(RESET);
+    }
+
+    result
+}
+
+// the first token goes in `[]` and the last in `{}`, so "eightwo" becomes
+// "[eigh{t]wo}" and "treb7uchet", where both are the same 7, "treb[{7}]uchet"
+fn highlight_brackets(line: &str, first: Token, last: Token) -> String {
+    let mut result = String::new();
+
+    for index in line
+        .char_indices()
+        .map(|(index, _)| index)
+        .chain([line.len()])
+    {
+        if last.end == index {
+            result.push('}');
+        }
+
+        if first.end == index {
+            result.push(']');
+        }
+
+        if first.start == index {
+            result.push('[');
+        }
+
+        if last.start == index {
+            result.push('{');
+        }
+
+        if let Some(character) = line[index..].chars().next() {
+            result.push(character);
+        }
+    }
+
+    result
+}
+
+pub fn highlight_first_and_last(line: &str, first: Token, last: Token, style: Style) -> String {
+    match style {
+        Style::Ansi => highlight_ansi(line, first, last),
+        Style::Brackets => highlight_brackets(line, first, last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(start: usize, end: usize, digit: u8) -> Token {
+        Token { start, end, digit }
+    }
+
+    #[test]
+    fn highlight_brackets_test() {
+        let style = Style::Brackets;
+
+        assert_eq!(
+            highlight_first_and_last("1abc2", token(0, 1, 1), token(4, 5, 2), style),
+            "[1]abc{2}"
+        );
+        assert_eq!(
+            highlight_first_and_last("treb7uchet", token(4, 5, 7), token(4, 5, 7), style),
+            "treb[{7}]uchet"
+        );
+        assert_eq!(
+            highlight_first_and_last("eightwo", token(0, 5, 8), token(4, 7, 2), style),
+            "[eigh{t]wo}"
+        );
+        assert_eq!(
+            highlight_first_and_last("12", token(0, 1, 1), token(1, 2, 2), style),
+            "[1]{2}"
+        );
+        assert_eq!(
+            highlight_first_and_last("åtteni", token(0, 5, 8), token(5, 7, 9), style),
+            "[åtte]{ni}"
+        );
+    }
+
+    #[test]
+    fn highlight_ansi_test() {
+        let style = Style::Ansi;
+
+        assert_eq!(
+            highlight_first_and_last("x1y2", token(1, 2, 1), token(3, 4, 2), style),
+            "x\x1b[1;32m1\x1b[0my\x1b[1;36m2\x1b[0m"
+        );
+        assert_eq!(
+            highlight_first_and_last("eightwo", token(0, 5, 8), token(4, 7, 2), style),
+            "\x1b[1;32meigh\x1b[1;33mt\x1b[1;36mwo\x1b[0m"
+        );
+        assert_eq!(
+            highlight_first_and_last("a7b", token(1, 2, 7), token(1, 2, 7), style),
+            "a\x1b[1;33m7\x1b[0mb"
+        );
+    }
+}
@@ -1,29 +1,1194 @@
-pub fn combine_first_and_last_number(first_number: &str, second_number: &str) -> i32 {
-    match format!("{}{}", first_number, second_number).parse::<i32>() {
-        Ok(result) => result,
-        Err(error) => panic!("Could not convert result to number: {:?}", error),
+use std::error::Error;
+use std::fmt;
+
+use regex::Regex;
+
+pub mod generator;
+pub mod highlight;
+pub mod number;
+pub mod unicode;
+pub mod vocabulary;
+
+use number::{combine_digits, CalibrationNumber};
+use unicode::decimal_digit_value;
+use vocabulary::Vocabulary;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DigitRules<'a> {
+    pub digit_set: DigitSet,
+    pub vocabulary: Option<&'a Vocabulary>,
+    pub overlap_policy: OverlapPolicy,
+}
+
+// which characters count as numeric digits, where `Unicode` also accepts any
+// decimal digit like the Arabic-Indic "٣" or the fullwidth "８"
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DigitSet {
+    Ascii,
+    Unicode,
+}
+
+// whether spelled digits may share letters, like "eightwo" being 8 and 2, or
+// whether each match consumes its letters from left to right, making it 8 only
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OverlapPolicy {
+    SharedLetters,
+    Consuming,
+}
+
+// which of a line's numbers make up its calibration value, where the puzzle
+// itself uses the first and the last
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Selection {
+    Ends { first: usize, last: usize },
+    All,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    Strict,
+    Lenient,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CalibrationValueError {
+    NoDigit,
+    Overflow,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CalibrationError {
+    NoDigit { line_number: usize, content: String },
+    Overflow { line_number: usize, content: String },
+}
+
+// a digit or spelled digit found in a line, `start..end` being its byte range
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Token {
+    pub start: usize,
+    pub end: usize,
+    pub digit: u8,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CalibrationDocument<'a> {
+    pub lines: Vec<&'a str>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct OverlapDifference<'a> {
+    pub line_number: usize,
+    pub content: &'a str,
+    pub shared_letters_value: Option<u64>,
+    pub consuming_value: Option<u64>,
+}
+
+impl DigitRules<'_> {
+    pub const NUMERIC: DigitRules<'static> = DigitRules {
+        digit_set: DigitSet::Ascii,
+        vocabulary: None,
+        overlap_policy: OverlapPolicy::SharedLetters,
+    };
+
+    pub fn numeric_and_spelled(
+        vocabulary: &Vocabulary,
+        overlap_policy: OverlapPolicy,
+    ) -> DigitRules<'_> {
+        DigitRules {
+            digit_set: DigitSet::Ascii,
+            vocabulary: Some(vocabulary),
+            overlap_policy,
+        }
+    }
+
+    pub fn with_digit_set(self, digit_set: DigitSet) -> Self {
+        DigitRules { digit_set, ..self }
+    }
+}
+
+impl Selection {
+    pub const FIRST_AND_LAST: Selection = Selection::Ends { first: 1, last: 1 };
+}
+
+pub fn combine_first_and_last_number<T: CalibrationNumber>(
+    first_number: u8,
+    last_number: u8,
+) -> Option<T> {
+    combine_digits(&[first_number, last_number])
+}
+
+fn match_capture(string: &str, vocabulary: &Vocabulary) -> u8 {
+    if let Some(value) = vocabulary.value_of(string) {
+        return value;
+    }
+
+    let mut characters = string.chars();
+
+    match (
+        characters.next().and_then(decimal_digit_value),
+        characters.next(),
+    ) {
+        (Some(value), None) => value,
+        _ => panic!("Could not convert `{string}` to a digit"),
+    }
+}
+
+fn digit_classes(digit_set: DigitSet) -> (&'static str, &'static str) {
+    match digit_set {
+        DigitSet::Ascii => ("[0-9]", "[^0-9]"),
+        DigitSet::Unicode => (r"\d", r"\D"),
+    }
+}
+
+fn number_pattern(digit_set: DigitSet, vocabulary: &Vocabulary) -> String {
+    let (digit, _) = digit_classes(digit_set);
+    let mut alternatives = vec![String::from(digit)];
+
+    for (word, _) in vocabulary.words.iter() {
+        alternatives.push(regex::escape(word));
+    }
+
+    alternatives.join("|")
+}
+
+fn first_and_last_number_regex(rules: DigitRules) -> Regex {
+    match rules.vocabulary {
+        None => {
+            let (digit, not_digit) = digit_classes(rules.digit_set);
+
+            Regex::new(&format!(
+                r"^{not_digit}*(?<first_number>{digit})(?:.*(?<second_number>{digit}){not_digit}*$)?"
+            ))
+            .unwrap()
+        }
+        Some(vocabulary) => {
+            let pattern = number_pattern(rules.digit_set, vocabulary);
+
+            Regex::new(&format!(
+                r"^.*?(?<first_number>{pattern})(?:.*(?<second_number>{pattern}))?.*?$"
+            ))
+            .unwrap()
+        }
+    }
+}
+
+// the regex reference for a set of rules, compiled once so that it can be
+// matched against many lines
+pub struct NumberRegex<'a> {
+    rules: DigitRules<'a>,
+    regex: Regex,
+}
+
+impl<'a> NumberRegex<'a> {
+    pub fn new(rules: DigitRules<'a>) -> NumberRegex<'a> {
+        let regex = match (rules.vocabulary, rules.overlap_policy) {
+            // `find_iter` never returns overlapping matches
+            (Some(vocabulary), OverlapPolicy::Consuming) => {
+                Regex::new(&number_pattern(rules.digit_set, vocabulary)).unwrap()
+            }
+            _ => first_and_last_number_regex(rules),
+        };
+
+        NumberRegex { rules, regex }
+    }
+
+    pub fn first_and_last_number(&self, string: &str) -> Option<(u8, u8)> {
+        let empty = Vocabulary { words: Vec::new() };
+        let vocabulary = self.rules.vocabulary.unwrap_or(&empty);
+
+        if let (Some(_), OverlapPolicy::Consuming) =
+            (self.rules.vocabulary, self.rules.overlap_policy)
+        {
+            let mut numbers = self
+                .regex
+                .find_iter(string)
+                .map(|number_match| match_capture(number_match.as_str(), vocabulary));
+
+            let first_number = numbers.next()?;
+            let last_number = numbers.last().unwrap_or(first_number);
+
+            return Some((first_number, last_number));
+        }
+
+        let captures = self.regex.captures(string)?;
+
+        let first_number = captures
+            .name("first_number")
+            .map_or(0, |capture| match_capture(capture.as_str(), vocabulary));
+
+        let second_number = captures
+            .name("second_number")
+            .map_or(first_number, |capture| {
+                match_capture(capture.as_str(), vocabulary)
+            });
+
+        Some((first_number, second_number))
+    }
+}
+
+// compiles the regex on every call, so a `NumberRegex` is much faster for
+// more than a few lines
+pub fn get_first_and_last_number_with_regex(string: &str, rules: DigitRules) -> Option<(u8, u8)> {
+    NumberRegex::new(rules).first_and_last_number(string)
+}
+
+// returns the digit and how many bytes of the string it spans
+fn number_starting_at(string: &str, index: usize, rules: DigitRules) -> Option<(u8, usize)> {
+    let rest = &string[index..];
+    let character = rest.chars().next()?;
+
+    let digit = match rules.digit_set {
+        DigitSet::Ascii => character.to_digit(10).map(|digit| digit as u8),
+        DigitSet::Unicode => decimal_digit_value(character),
+    };
+
+    if let Some(digit) = digit {
+        return Some((digit, character.len_utf8()));
     }
+
+    rules
+        .vocabulary?
+        .words
+        .iter()
+        .find(|(word, _)| rest.starts_with(word.as_str()))
+        .map(|(word, value)| (*value, word.len()))
+}
+
+fn token_starting_at(string: &str, index: usize, rules: DigitRules) -> Option<Token> {
+    number_starting_at(string, index, rules).map(|(digit, length)| Token {
+        start: index,
+        end: index + length,
+        digit,
+    })
+}
+
+fn tokens_forward<'a>(string: &'a str, rules: DigitRules<'a>) -> impl Iterator<Item = Token> + 'a {
+    string
+        .char_indices()
+        .filter_map(move |(index, _)| token_starting_at(string, index, rules))
 }
 
+fn tokens_backward<'a>(string: &'a str, rules: DigitRules<'a>) -> impl Iterator<Item = Token> + 'a {
+    string
+        .char_indices()
+        .rev()
+        .filter_map(move |(index, _)| token_starting_at(string, index, rules))
+}
+
+fn tokens_consuming(string: &str, rules: DigitRules) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while let Some(character) = string[index..].chars().next() {
+        match token_starting_at(string, index, rules) {
+            Some(token) => {
+                tokens.push(token);
+                index = token.end;
+            }
+            None => index += character.len_utf8(),
+        }
+    }
+
+    tokens
+}
+
+// with shared letters the first tokens are found scanning forward and the
+// last ones scanning backward from the end, so the middle of a long line is
+// never read. A line with fewer tokens than asked for reuses them, like
+// "treb7uchet" giving 7 and 7
+pub fn extract_tokens(string: &str, rules: DigitRules, selection: Selection) -> Option<Vec<Token>> {
+    let policy = match rules.vocabulary {
+        None => OverlapPolicy::SharedLetters,
+        Some(_) => rules.overlap_policy,
+    };
+
+    if let (OverlapPolicy::SharedLetters, Selection::Ends { first, last }) = (policy, selection) {
+        let mut forward = tokens_forward(string, rules).peekable();
+        forward.peek()?;
+
+        let mut tokens: Vec<Token> = forward.take(first).collect();
+        let mut last_tokens: Vec<Token> = tokens_backward(string, rules).take(last).collect();
+        last_tokens.reverse();
+        tokens.append(&mut last_tokens);
+
+        return Some(tokens);
+    }
+
+    let tokens = match policy {
+        OverlapPolicy::SharedLetters => tokens_forward(string, rules).collect(),
+        OverlapPolicy::Consuming => tokens_consuming(string, rules),
+    };
+
+    if tokens.is_empty() {
+        return None;
+    }
+
+    match selection {
+        Selection::All => Some(tokens),
+        Selection::Ends { first, last } => {
+            let first_tokens = &tokens[..first.min(tokens.len())];
+            let last_tokens = &tokens[tokens.len() - last.min(tokens.len())..];
+
+            Some([first_tokens, last_tokens].concat())
+        }
+    }
+}
+
+pub fn extract_digits(string: &str, rules: DigitRules, selection: Selection) -> Option<Vec<u8>> {
+    let tokens = extract_tokens(string, rules, selection)?;
+
+    Some(tokens.iter().map(|token| token.digit).collect())
+}
+
+pub fn get_first_and_last_number(string: &str, rules: DigitRules) -> Option<(u8, u8)> {
+    let digits = extract_digits(string, rules, Selection::FIRST_AND_LAST)?;
+
+    Some((digits[0], digits[1]))
+}
+
+pub fn calibration_value<T: CalibrationNumber>(
+    line: &str,
+    rules: DigitRules,
+    selection: Selection,
+) -> Result<T, CalibrationValueError> {
+    let digits = extract_digits(line, rules, selection).ok_or(CalibrationValueError::NoDigit)?;
+
+    combine_digits(&digits).ok_or(CalibrationValueError::Overflow)
+}
+
+pub fn parse_calibration_document(input: &str) -> CalibrationDocument<'_> {
+    CalibrationDocument {
+        lines: input.lines().collect(),
+    }
+}
+
+impl CalibrationDocument<'_> {
+    fn error(&self, index: usize, error: CalibrationValueError) -> CalibrationError {
+        let line_number = index + 1;
+        let content = self.lines[index].to_string();
+
+        match error {
+            CalibrationValueError::NoDigit => CalibrationError::NoDigit {
+                line_number,
+                content,
+            },
+            CalibrationValueError::Overflow => CalibrationError::Overflow {
+                line_number,
+                content,
+            },
+        }
+    }
+
+    // in lenient mode a line without any digit counts as 0
+    pub fn calibration_values<T: CalibrationNumber>(
+        &self,
+        rules: DigitRules,
+        selection: Selection,
+        mode: Mode,
+    ) -> Result<Vec<T>, CalibrationError> {
+        let mut calibration_values = Vec::new();
+
+        for (index, line) in self.lines.iter().enumerate() {
+            match (calibration_value(line, rules, selection), mode) {
+                (Ok(value), _) => calibration_values.push(value),
+                (Err(CalibrationValueError::NoDigit), Mode::Lenient) => {
+                    calibration_values.push(T::ZERO)
+                }
+                (Err(error), _) => return Err(self.error(index, error)),
+            }
+        }
+
+        Ok(calibration_values)
+    }
+
+    pub fn sum<T: CalibrationNumber>(
+        &self,
+        rules: DigitRules,
+        selection: Selection,
+        mode: Mode,
+    ) -> Result<T, CalibrationError> {
+        let mut sum = T::ZERO;
+
+        for (index, value) in self
+            .calibration_values(rules, selection, mode)?
+            .into_iter()
+            .enumerate()
+        {
+            sum = sum
+                .checked_add(value)
+                .ok_or_else(|| self.error(index, CalibrationValueError::Overflow))?;
+        }
+
+        Ok(sum)
+    }
+
+    pub fn overlap_differences(&self, vocabulary: &Vocabulary) -> Vec<OverlapDifference<'_>> {
+        let shared_letters_rules =
+            DigitRules::numeric_and_spelled(vocabulary, OverlapPolicy::SharedLetters);
+        let consuming_rules = DigitRules::numeric_and_spelled(vocabulary, OverlapPolicy::Consuming);
+
+        let mut differences = Vec::new();
+
+        for (index, line) in self.lines.iter().enumerate() {
+            let shared_letters_value =
+                calibration_value(line, shared_letters_rules, Selection::FIRST_AND_LAST).ok();
+            let consuming_value =
+                calibration_value(line, consuming_rules, Selection::FIRST_AND_LAST).ok();
+
+            if shared_letters_value != consuming_value {
+                differences.push(OverlapDifference {
+                    line_number: index + 1,
+                    content: line,
+                    shared_letters_value,
+                    consuming_value,
+                });
+            }
+        }
+
+        differences
+    }
+}
+
+impl fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalibrationError::NoDigit {
+                line_number,
+                content,
+            } => write!(
+                f,
+                "Line {line_number} does not contain any digit: `{content}`"
+            ),
+            CalibrationError::Overflow {
+                line_number,
+                content,
+            } => write!(
+                f,
+                "Calibration value or total overflows at line {line_number}: `{content}`"
+            ),
+        }
+    }
+}
+
+impl Error for CalibrationError {}
+
 #[cfg(test)]
 mod tests {
-    use crate::combine_first_and_last_number;
+    use super::*;
+    use crate::vocabulary::Language;
+    use common::random::Xorshift;
 
     #[test]
     fn combine_first_and_last_number_test() {
-        assert_eq!(combine_first_and_last_number("1", "2"), 12);
-        assert_eq!(combine_first_and_last_number("3", "8"), 38);
-        assert_eq!(combine_first_and_last_number("1", "5"), 15);
-        assert_eq!(combine_first_and_last_number("7", "7"), 77);
+        assert_eq!(combine_first_and_last_number::<u32>(1, 2), Some(12));
+        assert_eq!(combine_first_and_last_number::<u32>(3, 8), Some(38));
+        assert_eq!(combine_first_and_last_number::<u64>(1, 5), Some(15));
+        assert_eq!(combine_first_and_last_number::<i8>(7, 7), Some(77));
+        assert_eq!(combine_first_and_last_number::<u8>(0, 9), Some(9));
+    }
+
+    #[test]
+    fn match_capture_test() {
+        let english = Vocabulary::new(Language::English);
+
+        assert_eq!(match_capture("one", &english), 1);
+        assert_eq!(match_capture("two", &english), 2);
+        assert_eq!(match_capture("three", &english), 3);
+        assert_eq!(match_capture("four", &english), 4);
+        assert_eq!(match_capture("five", &english), 5);
+        assert_eq!(match_capture("six", &english), 6);
+        assert_eq!(match_capture("seven", &english), 7);
+        assert_eq!(match_capture("eight", &english), 8);
+        assert_eq!(match_capture("nine", &english), 9);
+        assert_eq!(match_capture("1", &english), 1);
+        assert_eq!(match_capture("4", &english), 4);
+        assert_eq!(match_capture("5", &english), 5);
+        assert_eq!(match_capture("9", &english), 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not convert `x` to a digit")]
+    fn match_capture_panic_test() {
+        let english = Vocabulary::new(Language::English);
+
+        match_capture("x", &english);
     }
 
     #[test]
-    #[should_panic(
-        expected = "Could not convert result to number: ParseIntError { kind: InvalidDigit }"
-    )]
-    fn combine_first_and_last_number_panic_test() {
-        assert_eq!(combine_first_and_last_number("a", "b"), 12);
-        assert_eq!(combine_first_and_last_number("1", "b"), 12);
-        assert_eq!(combine_first_and_last_number("h", "7"), 12);
+    fn number_starting_at_test() {
+        let english = Vocabulary::new(Language::English);
+
+        assert_eq!(
+            number_starting_at("a1b", 1, DigitRules::NUMERIC),
+            Some((1, 1))
+        );
+        assert_eq!(number_starting_at("a1b", 0, DigitRules::NUMERIC), None);
+        assert_eq!(number_starting_at("xtwone", 1, DigitRules::NUMERIC), None);
+        assert_eq!(
+            number_starting_at(
+                "xtwone",
+                1,
+                DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters)
+            ),
+            Some((2, 3))
+        );
+        assert_eq!(
+            number_starting_at(
+                "xtwone",
+                3,
+                DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters)
+            ),
+            Some((1, 3))
+        );
+        assert_eq!(
+            number_starting_at(
+                "xtwone",
+                4,
+                DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn get_first_and_last_number_numeric_test() {
+        let rules = DigitRules::NUMERIC;
+
+        assert_eq!(get_first_and_last_number("1abc2", rules), Some((1, 2)));
+        assert_eq!(
+            get_first_and_last_number("pqr3stu8vwx", rules),
+            Some((3, 8))
+        );
+        assert_eq!(
+            get_first_and_last_number("a1b2c3d4e5f", rules),
+            Some((1, 5))
+        );
+        assert_eq!(get_first_and_last_number("treb7uchet", rules), Some((7, 7)));
+        assert_eq!(get_first_and_last_number("two1nine", rules), Some((1, 1)));
+    }
+
+    #[test]
+    fn get_first_and_last_number_numeric_and_spelled_test() {
+        let english = Vocabulary::new(Language::English);
+
+        let rules = DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters);
+
+        assert_eq!(get_first_and_last_number("two1nine", rules), Some((2, 9)));
+        assert_eq!(
+            get_first_and_last_number("eightwothree", rules),
+            Some((8, 3))
+        );
+        assert_eq!(
+            get_first_and_last_number("abcone2threexyz", rules),
+            Some((1, 3))
+        );
+        assert_eq!(
+            get_first_and_last_number("7pqrstsixteen", rules),
+            Some((7, 6))
+        );
+        assert_eq!(get_first_and_last_number("6zfxp", rules), Some((6, 6)));
+        assert_eq!(get_first_and_last_number("eightwo", rules), Some((8, 2)));
+        assert_eq!(get_first_and_last_number("oneight", rules), Some((1, 8)));
+        assert_eq!(get_first_and_last_number("twone", rules), Some((2, 1)));
+        assert_eq!(get_first_and_last_number("xyz", rules), None);
+    }
+
+    #[test]
+    fn get_first_and_last_number_consuming_test() {
+        let english = Vocabulary::new(Language::English);
+        let rules = DigitRules::numeric_and_spelled(&english, OverlapPolicy::Consuming);
+
+        assert_eq!(get_first_and_last_number("eightwo", rules), Some((8, 8)));
+        assert_eq!(get_first_and_last_number("oneight", rules), Some((1, 1)));
+        assert_eq!(get_first_and_last_number("twone3", rules), Some((2, 3)));
+        assert_eq!(
+            get_first_and_last_number("xtwone3four", rules),
+            Some((2, 4))
+        );
+        assert_eq!(get_first_and_last_number("2eightwo", rules), Some((2, 8)));
+        assert_eq!(get_first_and_last_number("åtwoneå", rules), Some((2, 2)));
+        assert_eq!(get_first_and_last_number("xyz", rules), None);
+    }
+
+    #[test]
+    fn extract_digits_test() {
+        let english = Vocabulary::new(Language::English);
+        let shared = DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters);
+        let consuming = DigitRules::numeric_and_spelled(&english, OverlapPolicy::Consuming);
+        let first_two_and_last_two = Selection::Ends { first: 2, last: 2 };
+
+        assert_eq!(
+            extract_digits("1abc2def3gh4", DigitRules::NUMERIC, first_two_and_last_two),
+            Some(vec![1, 2, 3, 4])
+        );
+        assert_eq!(
+            extract_digits("a1b2c", DigitRules::NUMERIC, first_two_and_last_two),
+            Some(vec![1, 2, 1, 2])
+        );
+        assert_eq!(
+            extract_digits("treb7uchet", DigitRules::NUMERIC, first_two_and_last_two),
+            Some(vec![7, 7])
+        );
+        assert_eq!(
+            extract_digits(
+                "x1y2z3",
+                DigitRules::NUMERIC,
+                Selection::Ends { first: 2, last: 0 }
+            ),
+            Some(vec![1, 2])
+        );
+        assert_eq!(
+            extract_digits(
+                "x1y2z3",
+                DigitRules::NUMERIC,
+                Selection::Ends { first: 0, last: 2 }
+            ),
+            Some(vec![2, 3])
+        );
+        assert_eq!(
+            extract_digits("x1y2z3", DigitRules::NUMERIC, Selection::All),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(
+            extract_digits("4nineeightseven2", shared, Selection::All),
+            Some(vec![4, 9, 8, 7, 2])
+        );
+        assert_eq!(
+            extract_digits("eightwone", shared, Selection::All),
+            Some(vec![8, 2, 1])
+        );
+        assert_eq!(
+            extract_digits("eightwone", consuming, Selection::All),
+            Some(vec![8, 1])
+        );
+        assert_eq!(
+            extract_digits("eightwone7", consuming, first_two_and_last_two),
+            Some(vec![8, 1, 1, 7])
+        );
+        assert_eq!(extract_digits("xyz", shared, Selection::All), None);
+        assert_eq!(
+            extract_digits("xyz", shared, Selection::Ends { first: 0, last: 0 }),
+            None
+        );
+    }
+
+    #[test]
+    fn extract_tokens_test() {
+        let english = Vocabulary::new(Language::English);
+        let shared = DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters);
+        let consuming = DigitRules::numeric_and_spelled(&english, OverlapPolicy::Consuming);
+
+        let eight = Token {
+            start: 1,
+            end: 6,
+            digit: 8,
+        };
+        let two = Token {
+            start: 5,
+            end: 8,
+            digit: 2,
+        };
+
+        assert_eq!(
+            extract_tokens("xeightwo", shared, Selection::FIRST_AND_LAST),
+            Some(vec![eight, two])
+        );
+        assert_eq!(
+            extract_tokens("xeightwo", consuming, Selection::FIRST_AND_LAST),
+            Some(vec![eight, eight])
+        );
+        assert_eq!(
+            extract_tokens("åtwo", shared, Selection::All),
+            Some(vec![Token {
+                start: 2,
+                end: 5,
+                digit: 2,
+            }])
+        );
+        assert_eq!(extract_tokens("xyz", shared, Selection::All), None);
+    }
+
+    #[test]
+    fn extract_digits_scanner_matches_full_pass_test() {
+        let english = Vocabulary::new(Language::English);
+        let rules = DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters);
+        let file = common::read_input(1);
+
+        for line in file.lines() {
+            let all = extract_digits(line, rules, Selection::All).unwrap();
+
+            for (first, last) in [(1, 1), (2, 2), (3, 0), (0, 3)] {
+                let first_digits = &all[..first.min(all.len())];
+                let last_digits = &all[all.len() - last.min(all.len())..];
+
+                assert_eq!(
+                    extract_digits(line, rules, Selection::Ends { first, last }),
+                    Some([first_digits, last_digits].concat()),
+                    "{line}"
+                );
+            }
+        }
+    }
+
+    fn generate_line(random: &mut Xorshift) -> String {
+        let pieces = [
+            "one",
+            "two",
+            "three",
+            "four",
+            "five",
+            "six",
+            "seven",
+            "eight",
+            "nine",
+            "eightwo",
+            "oneight",
+            "twone",
+            "sevenine",
+            "threeight",
+            "nin",
+            "tw",
+            "on",
+            "eigh",
+            "x",
+            "q",
+            "e",
+            "n",
+            "t",
+        ];
+
+        let mut line = String::new();
+        let piece_count = 1 + random.up_to(11);
+        let digit_position = random.up_to(piece_count - 1);
+
+        for piece_index in 0..piece_count {
+            if piece_index == digit_position {
+                line.push(char::from(b'1' + random.up_to(8) as u8));
+            }
+
+            line.push_str(pieces[random.up_to(pieces.len() - 1)]);
+        }
+
+        line
+    }
+
+    #[test]
+    fn scanner_matches_regex_on_input_test() {
+        let english = Vocabulary::new(Language::English);
+        let file = common::read_input(1);
+
+        for rules in [
+            DigitRules::NUMERIC,
+            DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters),
+            DigitRules::numeric_and_spelled(&english, OverlapPolicy::Consuming),
+        ] {
+            let number_regex = NumberRegex::new(rules);
+
+            for line in file.lines() {
+                assert_eq!(
+                    get_first_and_last_number(line, rules),
+                    number_regex.first_and_last_number(line),
+                    "{line}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn scanner_matches_regex_on_generated_lines_test() {
+        let english = Vocabulary::new(Language::English);
+        let mut random = Xorshift::new(0x2023_1201);
+        let lines: Vec<String> = (0..500).map(|_| generate_line(&mut random)).collect();
+
+        for rules in [
+            DigitRules::NUMERIC,
+            DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters),
+            DigitRules::numeric_and_spelled(&english, OverlapPolicy::Consuming),
+        ] {
+            let number_regex = NumberRegex::new(rules);
+
+            for line in lines.iter() {
+                assert_eq!(
+                    get_first_and_last_number(line, rules),
+                    number_regex.first_and_last_number(line),
+                    "{line}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn get_first_and_last_number_digit_set_test() {
+        let english = Vocabulary::new(Language::English);
+        let ascii = DigitRules::NUMERIC;
+        let unicode = DigitRules::NUMERIC.with_digit_set(DigitSet::Unicode);
+        let spelled = DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters)
+            .with_digit_set(DigitSet::Unicode);
+
+        assert_eq!(get_first_and_last_number("٣abc٧", ascii), None);
+        assert_eq!(get_first_and_last_number("٣abc٧", unicode), Some((3, 7)));
+        assert_eq!(get_first_and_last_number("a٣b5c", ascii), Some((5, 5)));
+        assert_eq!(get_first_and_last_number("a٣b5c", unicode), Some((3, 5)));
+        assert_eq!(get_first_and_last_number("８ninex", spelled), Some((8, 9)));
+        assert_eq!(get_first_and_last_number("x𝟘y४", unicode), Some((0, 4)));
+        assert_eq!(get_first_and_last_number("²½Ⅻ", unicode), None);
+
+        assert_eq!(
+            extract_tokens("८two", spelled, Selection::All),
+            Some(vec![
+                Token {
+                    start: 0,
+                    end: 3,
+                    digit: 8
+                },
+                Token {
+                    start: 3,
+                    end: 6,
+                    digit: 2
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn scanner_matches_regex_with_unicode_digits_test() {
+        let english = Vocabulary::new(Language::English);
+        let others = ["٣", "８", "४", "𝟡", "৭", "²", "½", "é"];
+        let mut random = Xorshift::new(0x2023_1219);
+
+        let lines: Vec<String> = (0..500)
+            .map(|_| {
+                let mut line = generate_line(&mut random);
+                let position = line
+                    .char_indices()
+                    .nth(random.up_to(line.chars().count() - 1))
+                    .map_or(0, |(index, _)| index);
+                line.insert_str(position, others[random.up_to(others.len() - 1)]);
+
+                line
+            })
+            .collect();
+
+        for digit_set in [DigitSet::Ascii, DigitSet::Unicode] {
+            for rules in [
+                DigitRules::NUMERIC,
+                DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters),
+                DigitRules::numeric_and_spelled(&english, OverlapPolicy::Consuming),
+            ] {
+                let rules = rules.with_digit_set(digit_set);
+                let number_regex = NumberRegex::new(rules);
+
+                for line in lines.iter() {
+                    assert_eq!(
+                        get_first_and_last_number(line, rules),
+                        number_regex.first_and_last_number(line),
+                        "{line}"
+                    );
+                }
+            }
+        }
+    }
+
+    // timing based, so run it on its own with `cargo test --release -- --ignored`
+    #[test]
+    #[ignore]
+    fn scanner_is_faster_than_regex_test() {
+        let english = Vocabulary::new(Language::English);
+        let file = common::read_input(1);
+        let rules = DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters);
+
+        let scanner_start = std::time::Instant::now();
+        for line in file.lines() {
+            get_first_and_last_number(line, rules);
+        }
+        let scanner_duration = scanner_start.elapsed();
+
+        // compiled outside the timed loop, so only the matching is measured
+        let number_regex = NumberRegex::new(rules);
+
+        let regex_start = std::time::Instant::now();
+        for line in file.lines() {
+            number_regex.first_and_last_number(line);
+        }
+        let regex_duration = regex_start.elapsed();
+
+        println!("scanner: {scanner_duration:?}, regex: {regex_duration:?}");
+
+        assert!(scanner_duration < regex_duration);
+    }
+
+    #[test]
+    fn get_first_and_last_number_other_vocabularies_test() {
+        let norwegian = Vocabulary::new(Language::Norwegian);
+        let german = Vocabulary::with_zero(Language::German);
+        let mut custom = Vocabulary { words: Vec::new() };
+        custom.add_word("uno", 1);
+        custom.add_word("zero", 0);
+
+        let norwegian_rules =
+            DigitRules::numeric_and_spelled(&norwegian, OverlapPolicy::SharedLetters);
+        let german_rules = DigitRules::numeric_and_spelled(&german, OverlapPolicy::SharedLetters);
+        let custom_rules = DigitRules::numeric_and_spelled(&custom, OverlapPolicy::SharedLetters);
+
+        assert_eq!(
+            get_first_and_last_number("xtrefemx", norwegian_rules),
+            Some((3, 5))
+        );
+        assert_eq!(
+            get_first_and_last_number("åtteni", norwegian_rules),
+            Some((8, 9))
+        );
+        assert_eq!(
+            get_first_and_last_number("entre", norwegian_rules),
+            Some((1, 3))
+        );
+        assert_eq!(
+            get_first_and_last_number("nullfünf7", german_rules),
+            Some((0, 7))
+        );
+        assert_eq!(
+            get_first_and_last_number("unozero", custom_rules),
+            Some((1, 0))
+        );
+        assert_eq!(
+            get_first_and_last_number("one2", custom_rules),
+            Some((2, 2))
+        );
+    }
+
+    #[test]
+    fn scanner_matches_regex_with_other_vocabularies_test() {
+        let norwegian = Vocabulary::with_zero(Language::Norwegian);
+        let german = Vocabulary::with_zero(Language::German);
+        let lines = [
+            "4tonullsju",
+            "x1seksåttefem",
+            "einsneunzweiacht3",
+            "2fünfsechsnull",
+        ];
+
+        for line in lines {
+            for vocabulary in [&norwegian, &german] {
+                let rules =
+                    DigitRules::numeric_and_spelled(vocabulary, OverlapPolicy::SharedLetters);
+
+                assert_eq!(
+                    get_first_and_last_number(line, rules),
+                    get_first_and_last_number_with_regex(line, rules),
+                    "{line}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn calibration_value_test() {
+        let english = Vocabulary::new(Language::English);
+
+        assert_eq!(
+            calibration_value::<u32>(
+                "pqr3stu8vwx",
+                DigitRules::NUMERIC,
+                Selection::FIRST_AND_LAST
+            ),
+            Ok(38)
+        );
+        assert_eq!(
+            calibration_value::<u32>(
+                "xtwone3four",
+                DigitRules::NUMERIC,
+                Selection::FIRST_AND_LAST
+            ),
+            Ok(33)
+        );
+        assert_eq!(
+            calibration_value::<u32>(
+                "xtwone3four",
+                DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters),
+                Selection::FIRST_AND_LAST
+            ),
+            Ok(24)
+        );
+        assert_eq!(
+            calibration_value::<u32>(
+                "zoneight234",
+                DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters),
+                Selection::FIRST_AND_LAST
+            ),
+            Ok(14)
+        );
+        assert_eq!(
+            calibration_value::<u32>("xyz", DigitRules::NUMERIC, Selection::FIRST_AND_LAST),
+            Err(CalibrationValueError::NoDigit)
+        );
+    }
+
+    #[test]
+    fn calibration_value_selection_test() {
+        assert_eq!(
+            calibration_value::<u32>(
+                "1abc2def3gh4",
+                DigitRules::NUMERIC,
+                Selection::Ends { first: 2, last: 2 }
+            ),
+            Ok(1234)
+        );
+        assert_eq!(
+            calibration_value::<u64>("a1b2c3d4e5f", DigitRules::NUMERIC, Selection::All),
+            Ok(12345)
+        );
+        assert_eq!(
+            calibration_value::<u16>("a1b2c3d4e5f6", DigitRules::NUMERIC, Selection::All),
+            Err(CalibrationValueError::Overflow)
+        );
+    }
+
+    #[test]
+    fn calibration_document_mode_test() {
+        let document = parse_calibration_document("1abc2\nabcdef\ntreb7uchet");
+
+        assert_eq!(
+            document.calibration_values::<u32>(
+                DigitRules::NUMERIC,
+                Selection::FIRST_AND_LAST,
+                Mode::Lenient
+            ),
+            Ok(vec![12, 0, 77])
+        );
+        assert_eq!(
+            document.sum::<u32>(
+                DigitRules::NUMERIC,
+                Selection::FIRST_AND_LAST,
+                Mode::Lenient
+            ),
+            Ok(89)
+        );
+        assert_eq!(
+            document.sum::<u32>(DigitRules::NUMERIC, Selection::FIRST_AND_LAST, Mode::Strict),
+            Err(CalibrationError::NoDigit {
+                line_number: 2,
+                content: String::from("abcdef"),
+            })
+        );
+    }
+
+    #[test]
+    fn overlap_differences_test() {
+        let english = Vocabulary::new(Language::English);
+        let document = parse_calibration_document(
+            "two1nine\neightwothree\nxtwone\nzoneight234\n7pqrstsixteen\n3sevenine\nabc",
+        );
+
+        assert_eq!(
+            document.overlap_differences(&english),
+            vec![
+                OverlapDifference {
+                    line_number: 3,
+                    content: "xtwone",
+                    shared_letters_value: Some(21),
+                    consuming_value: Some(22),
+                },
+                OverlapDifference {
+                    line_number: 6,
+                    content: "3sevenine",
+                    shared_letters_value: Some(39),
+                    consuming_value: Some(37),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn calibration_document_overflow_test() {
+        let document = parse_calibration_document("99\n99\n99\n99");
+
+        assert_eq!(
+            document.calibration_values::<u8>(
+                DigitRules::NUMERIC,
+                Selection::FIRST_AND_LAST,
+                Mode::Strict
+            ),
+            Ok(vec![99, 99, 99, 99])
+        );
+        assert_eq!(
+            document.sum::<u8>(DigitRules::NUMERIC, Selection::FIRST_AND_LAST, Mode::Strict),
+            Err(CalibrationError::Overflow {
+                line_number: 3,
+                content: String::from("99"),
+            })
+        );
+        assert_eq!(
+            document.sum::<u16>(DigitRules::NUMERIC, Selection::FIRST_AND_LAST, Mode::Strict),
+            Ok(396)
+        );
+    }
+
+    #[test]
+    fn calibration_error_display_test() {
+        let error = CalibrationError::NoDigit {
+            line_number: 2,
+            content: String::from("abcdef"),
+        };
+
+        let overflow_error = CalibrationError::Overflow {
+            line_number: 3,
+            content: String::from("99"),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Line 2 does not contain any digit: `abcdef`"
+        );
+        assert_eq!(
+            overflow_error.to_string(),
+            "Calibration value or total overflows at line 3: `99`"
+        );
+    }
+
+    #[test]
+    fn calibration_document_test() {
+        let english = Vocabulary::new(Language::English);
+
+        let part_1_document =
+            parse_calibration_document("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet");
+        let part_2_document = parse_calibration_document(
+            "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n",
+        );
+
+        assert_eq!(part_1_document.lines.len(), 4);
+        assert_eq!(
+            part_1_document.calibration_values::<u32>(
+                DigitRules::NUMERIC,
+                Selection::FIRST_AND_LAST,
+                Mode::Strict
+            ),
+            Ok(vec![12, 38, 15, 77])
+        );
+        assert_eq!(
+            part_1_document.sum::<u32>(
+                DigitRules::NUMERIC,
+                Selection::FIRST_AND_LAST,
+                Mode::Strict
+            ),
+            Ok(142)
+        );
+
+        assert_eq!(part_2_document.lines.len(), 7);
+        assert_eq!(
+            part_2_document.calibration_values::<u32>(
+                DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters),
+                Selection::FIRST_AND_LAST,
+                Mode::Strict
+            ),
+            Ok(vec![29, 83, 13, 24, 42, 14, 76])
+        );
+        assert_eq!(
+            part_2_document.sum::<u32>(
+                DigitRules::numeric_and_spelled(&english, OverlapPolicy::SharedLetters),
+                Selection::FIRST_AND_LAST,
+                Mode::Strict
+            ),
+            Ok(281)
+        );
     }
 }
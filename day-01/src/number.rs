@@ -0,0 +1,68 @@
+pub trait CalibrationNumber: Copy {
+    const ZERO: Self;
+
+    fn from_u8(value: u8) -> Option<Self>;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_calibration_number {
+    ($($integer:ty),*) => {
+        $(
+            impl CalibrationNumber for $integer {
+                const ZERO: Self = 0;
+
+                fn from_u8(value: u8) -> Option<Self> {
+                    <$integer>::try_from(value).ok()
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$integer>::checked_add(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$integer>::checked_mul(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_calibration_number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+// reads the digits as one decimal number, or `None` if it does not fit in `T`
+pub fn combine_digits<T: CalibrationNumber>(digits: &[u8]) -> Option<T> {
+    let ten = T::from_u8(10)?;
+    let mut result = T::ZERO;
+
+    for digit in digits {
+        result = result.checked_mul(ten)?.checked_add(T::from_u8(*digit)?)?;
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combine_digits_test() {
+        assert_eq!(combine_digits::<u32>(&[1, 2]), Some(12));
+        assert_eq!(combine_digits::<u32>(&[0, 7]), Some(7));
+        assert_eq!(combine_digits::<u32>(&[]), Some(0));
+        assert_eq!(combine_digits::<i8>(&[9, 9]), Some(99));
+        assert_eq!(combine_digits::<u8>(&[2, 5, 5]), Some(255));
+        assert_eq!(
+            combine_digits::<u128>(&[3, 4, 0, 2, 8, 2, 3, 6, 6, 9]),
+            Some(3402823669)
+        );
+    }
+
+    #[test]
+    fn combine_digits_overflow_test() {
+        assert_eq!(combine_digits::<u8>(&[2, 5, 6]), None);
+        assert_eq!(combine_digits::<i8>(&[1, 2, 8]), None);
+        assert_eq!(combine_digits::<u32>(&[4, 2, 9, 4, 9, 6, 7, 2, 9, 6]), None);
+    }
+}
@@ -0,0 +1,62 @@
+// the zero of every run of decimal digits (general category Nd) in Unicode
+// 16.0, the version the `regex` crate's `\d` follows. Each run is the ten
+// digits 0 to 9 in order, so the value of a digit is its distance from the
+// zero before it
+const DECIMAL_DIGIT_ZEROS: [u32; 76] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x10D40, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450,
+    0x114D0, 0x11650, 0x116C0, 0x116D0, 0x116DA, 0x11730, 0x118E0, 0x11950, 0x11BF0, 0x11C50,
+    0x11D50, 0x11DA0, 0x11F50, 0x16130, 0x16A60, 0x16AC0, 0x16B50, 0x16D70, 0x1CCF0, 0x1D7CE,
+    0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E5F1, 0x1E950, 0x1FBF0,
+];
+
+pub fn decimal_digit_value(character: char) -> Option<u8> {
+    let code_point = character as u32;
+    let zeros_before = DECIMAL_DIGIT_ZEROS.partition_point(|zero| *zero <= code_point);
+
+    let zero = DECIMAL_DIGIT_ZEROS[..zeros_before].last()?;
+    let value = code_point - zero;
+
+    if value < 10 {
+        Some(value as u8)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimal_digit_value_test() {
+        assert_eq!(decimal_digit_value('0'), Some(0));
+        assert_eq!(decimal_digit_value('7'), Some(7));
+        assert_eq!(decimal_digit_value('٣'), Some(3));
+        assert_eq!(decimal_digit_value('۹'), Some(9));
+        assert_eq!(decimal_digit_value('५'), Some(5));
+        assert_eq!(decimal_digit_value('８'), Some(8));
+        assert_eq!(decimal_digit_value('𝟘'), Some(0));
+        assert_eq!(decimal_digit_value('𝟡'), Some(9));
+        assert_eq!(decimal_digit_value('a'), None);
+        assert_eq!(decimal_digit_value('/'), None);
+        assert_eq!(decimal_digit_value(':'), None);
+        assert_eq!(decimal_digit_value('½'), None);
+        assert_eq!(decimal_digit_value('Ⅻ'), None);
+    }
+
+    #[test]
+    fn decimal_digit_value_matches_regex_test() {
+        let digit_regex = regex::Regex::new(r"^\d$").unwrap();
+
+        for character in (0..=0x1FFFF).filter_map(char::from_u32) {
+            assert_eq!(
+                decimal_digit_value(character).is_some(),
+                digit_regex.is_match(character.encode_utf8(&mut [0; 4])),
+                "{character:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Language {
+    English,
+    Norwegian,
+    German,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Vocabulary {
+    pub words: Vec<(String, u8)>,
+}
+
+impl Language {
+    // index is the value of the word, so index 0 is the word for zero
+    fn words(&self) -> [&'static str; 10] {
+        match self {
+            Language::English => [
+                "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+            ],
+            Language::Norwegian => [
+                "null", "en", "to", "tre", "fire", "fem", "seks", "sju", "åtte", "ni",
+            ],
+            Language::German => [
+                "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+            ],
+        }
+    }
+}
+
+impl Vocabulary {
+    pub fn new(language: Language) -> Vocabulary {
+        let mut vocabulary = Vocabulary::with_zero(language);
+        vocabulary.words.remove(0);
+
+        vocabulary
+    }
+
+    pub fn with_zero(language: Language) -> Vocabulary {
+        let mut vocabulary = Vocabulary { words: Vec::new() };
+
+        for (value, word) in language.words().iter().enumerate() {
+            vocabulary.add_word(word, value as u8);
+        }
+
+        vocabulary
+    }
+
+    pub fn add_word(&mut self, word: &str, value: u8) {
+        if word.is_empty() {
+            panic!("Spelled digit cannot be empty");
+        }
+
+        if value > 9 {
+            panic!("Value of spelled digit `{word}` is not a single digit: {value}");
+        }
+
+        self.words.push((word.to_string(), value));
+    }
+
+    pub fn value_of(&self, word: &str) -> Option<u8> {
+        self.words
+            .iter()
+            .find(|(known_word, _)| known_word == word)
+            .map(|(_, value)| *value)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum VocabularyParseError {
+    MissingEquals(String),
+    EmptyWord(String),
+    BadValue(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineParseError {
+    pub line_number: usize,
+    pub error: VocabularyParseError,
+}
+
+#[derive(Debug)]
+pub enum ReadVocabularyError {
+    Io { path: String, error: io::Error },
+    Parse(Vec<LineParseError>),
+}
+
+fn parse_word(line: &str) -> Result<(&str, u8), VocabularyParseError> {
+    let (word, value) = line
+        .split_once('=')
+        .ok_or_else(|| VocabularyParseError::MissingEquals(line.to_string()))?;
+    let (word, value) = (word.trim(), value.trim());
+
+    if word.is_empty() {
+        return Err(VocabularyParseError::EmptyWord(line.to_string()));
+    }
+
+    match value.parse::<u8>() {
+        Ok(value) if value <= 9 => Ok((word, value)),
+        _ => Err(VocabularyParseError::BadValue(value.to_string())),
+    }
+}
+
+// one `word=value` pair per line, blank lines and lines starting with `#` are
+// skipped. Every line is parsed, so all of the errors are reported at once
+pub fn parse_vocabulary(input: &str) -> Result<Vocabulary, Vec<LineParseError>> {
+    let mut vocabulary = Vocabulary { words: Vec::new() };
+    let mut errors = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match parse_word(line) {
+            Ok((word, value)) => vocabulary.add_word(word, value),
+            Err(error) => errors.push(LineParseError {
+                line_number: index + 1,
+                error,
+            }),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(vocabulary)
+    } else {
+        Err(errors)
+    }
+}
+
+// the path is taken relative to the current directory
+pub fn read_vocabulary(path: &str) -> Result<Vocabulary, ReadVocabularyError> {
+    let input = fs::read_to_string(path).map_err(|error| ReadVocabularyError::Io {
+        path: path.to_string(),
+        error,
+    })?;
+
+    parse_vocabulary(&input).map_err(ReadVocabularyError::Parse)
+}
+
+impl fmt::Display for VocabularyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VocabularyParseError::MissingEquals(text) => {
+                write!(f, "Expected `word=value` in vocabulary, got `{text}`")
+            }
+            VocabularyParseError::EmptyWord(text) => {
+                write!(f, "Spelled digit cannot be empty: `{text}`")
+            }
+            VocabularyParseError::BadValue(text) => {
+                write!(f, "Expected a single digit as the value, got `{text}`")
+            }
+        }
+    }
+}
+
+impl Error for VocabularyParseError {}
+
+impl fmt::Display for LineParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Line {}: {}", self.line_number, self.error)
+    }
+}
+
+impl Error for LineParseError {}
+
+impl fmt::Display for ReadVocabularyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadVocabularyError::Io { path, error } => {
+                write!(f, "Could not read vocabulary from `{path}`: {error}")
+            }
+            ReadVocabularyError::Parse(errors) => {
+                let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+
+                write!(f, "{}", errors.join("\n"))
+            }
+        }
+    }
+}
+
+impl Error for ReadVocabularyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_test() {
+        let english = Vocabulary::new(Language::English);
+        let norwegian = Vocabulary::new(Language::Norwegian);
+
+        assert_eq!(english.words.len(), 9);
+        assert_eq!(english.words[0], ("one".to_string(), 1));
+        assert_eq!(english.value_of("nine"), Some(9));
+        assert_eq!(english.value_of("zero"), None);
+
+        assert_eq!(norwegian.value_of("åtte"), Some(8));
+        assert_eq!(norwegian.value_of("null"), None);
+    }
+
+    #[test]
+    fn with_zero_test() {
+        let german = Vocabulary::with_zero(Language::German);
+
+        assert_eq!(german.words.len(), 10);
+        assert_eq!(german.value_of("null"), Some(0));
+        assert_eq!(german.value_of("fünf"), Some(5));
+    }
+
+    #[test]
+    #[should_panic(expected = "Value of spelled digit `ten` is not a single digit: 10")]
+    fn add_word_panic_test() {
+        let mut vocabulary = Vocabulary::new(Language::English);
+        vocabulary.add_word("ten", 10);
+    }
+
+    #[test]
+    fn parse_vocabulary_test() {
+        let vocabulary = parse_vocabulary("# custom\nuno = 1\n\ndos=2\n  tres=3  \n").unwrap();
+
+        assert_eq!(
+            vocabulary.words,
+            vec![
+                ("uno".to_string(), 1),
+                ("dos".to_string(), 2),
+                ("tres".to_string(), 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_vocabulary_errors_test() {
+        assert_eq!(
+            parse_vocabulary("uno 1\ndos=2\n=3\ndiez=10\ncuatro=x"),
+            Err(vec![
+                LineParseError {
+                    line_number: 1,
+                    error: VocabularyParseError::MissingEquals(String::from("uno 1")),
+                },
+                LineParseError {
+                    line_number: 3,
+                    error: VocabularyParseError::EmptyWord(String::from("=3")),
+                },
+                LineParseError {
+                    line_number: 4,
+                    error: VocabularyParseError::BadValue(String::from("10")),
+                },
+                LineParseError {
+                    line_number: 5,
+                    error: VocabularyParseError::BadValue(String::from("x")),
+                },
+            ])
+        );
+        assert_eq!(
+            parse_vocabulary("uno 1").unwrap_err()[0].to_string(),
+            "Line 1: Expected `word=value` in vocabulary, got `uno 1`"
+        );
+    }
+
+    #[test]
+    fn read_vocabulary_test() {
+        let path = std::env::temp_dir().join("day-01-read-vocabulary-test.txt");
+        fs::write(&path, "uno=1\ndos=2\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        assert_eq!(
+            read_vocabulary(path).unwrap().words,
+            vec![("uno".to_string(), 1), ("dos".to_string(), 2)]
+        );
+
+        fs::write(path, "uno\n").unwrap();
+
+        assert_eq!(
+            read_vocabulary(path).unwrap_err().to_string(),
+            "Line 1: Expected `word=value` in vocabulary, got `uno`"
+        );
+
+        fs::remove_file(path).unwrap();
+
+        assert!(matches!(
+            read_vocabulary(path),
+            Err(ReadVocabularyError::Io { .. })
+        ));
+    }
+}
@@ -36,52 +36,173 @@
     of the IDs of those games?
 */
 
+use std::env;
+use std::fs;
+use std::process;
+
 use common::read_input;
-use day_02_lib::parse_game;
+use day_02_lib::cube_set::CubeSet;
+use day_02_lib::{parse_bag, parse_bags, parse_game, parse_games, possible_game_ids, Game};
 
-const MAX_GREEN_CUBES: i32 = 13;
-const MAX_BLUE_CUBES: i32 = 14;
-const MAX_RED_CUBES: i32 = 12;
+const PUZZLE_BAG: &str = "red=12,green=13,blue=14";
 
 fn part_1() -> i32 {
     let file = read_input(2);
+    let bag = parse_bag(PUZZLE_BAG).unwrap();
 
     let mut sum: i32 = 0;
 
     for line in file.split("\n") {
-        let game = parse_game(&line);
-        let mut skip = false;
-
-        for round in game.rounds.iter() {
-            if round.green_cubes > MAX_GREEN_CUBES
-                || round.blue_cubes > MAX_BLUE_CUBES
-                || round.red_cubes > MAX_RED_CUBES
-            {
-                skip = true;
-                break;
-            }
-        }
+        let game = parse_game(line);
 
-        if skip {
-            continue;
+        if game.is_possible_with(&bag) {
+            sum += game.id;
         }
-
-        sum += game.id;
     }
 
     sum
 }
 
+fn parse_arguments(arguments: &[String]) -> Result<Vec<CubeSet>, String> {
+    let mut bags = Vec::new();
+    let mut arguments = arguments.iter();
+
+    while let Some(argument) = arguments.next() {
+        let value = arguments
+            .next()
+            .ok_or_else(|| format!("Expected a value after `{argument}`"))?;
+
+        match argument.as_str() {
+            "--bag" => bags.push(parse_bag(value).map_err(|error| error.to_string())?),
+            "--bags" => {
+                let file = fs::read_to_string(value)
+                    .map_err(|error| format!("Could not read bags from `{value}`: {error}"))?;
+
+                match parse_bags(&file) {
+                    Ok(file_bags) => bags.extend(file_bags),
+                    Err(errors) => {
+                        let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+                        return Err(errors.join("\n"));
+                    }
+                }
+            }
+            other => return Err(format!("Unknown option `{other}`")),
+        }
+    }
+
+    Ok(bags)
+}
+
+fn format_bag(bag: &CubeSet) -> String {
+    let cubes: Vec<String> = bag
+        .cubes
+        .iter()
+        .map(|(colour, amount)| format!("{colour}={amount}"))
+        .collect();
+
+    cubes.join(",")
+}
+
+fn format_possible_games(games: &[Game], bag: &CubeSet) -> String {
+    let ids = possible_game_ids(games, bag);
+    let sum: i32 = ids.iter().sum();
+    let ids: Vec<String> = ids.iter().map(ToString::to_string).collect();
+
+    format!("{}: sum {}, ids {}", format_bag(bag), sum, ids.join(", "))
+}
+
+// without arguments this prints the answer for the puzzle's bag. Otherwise
+// bags are given with `--bag red=12,green=13,blue=14`, as often as needed,
+// or `--bags <file>` with one bag per line, and the possible game ids and
+// their sum are printed for each of them
 fn main() {
-    println!("{}", part_1());
+    let arguments: Vec<String> = env::args().skip(1).collect();
+
+    if arguments.is_empty() {
+        println!("{}", part_1());
+        return;
+    }
+
+    let result = parse_arguments(&arguments).and_then(|bags| {
+        let games = parse_games(&read_input(2)).map_err(|errors| {
+            let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            errors.join("\n")
+        })?;
+
+        Ok((bags, games))
+    });
+
+    match result {
+        Ok((bags, games)) => {
+            for bag in bags.iter() {
+                println!("{}", format_possible_games(&games, bag));
+            }
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::part_1;
+    use super::*;
 
     #[test]
     fn part_1_test() {
         assert_eq!(part_1(), 2632)
     }
+
+    #[test]
+    fn parse_arguments_test() {
+        let arguments =
+            |line: &str| -> Vec<String> { line.split_whitespace().map(String::from).collect() };
+
+        assert_eq!(
+            parse_arguments(&arguments("--bag red=12,green=13,blue=14 --bag gold=1")),
+            Ok(vec![
+                CubeSet::from([("red", 12), ("green", 13), ("blue", 14)]),
+                CubeSet::from([("gold", 1)])
+            ])
+        );
+        assert_eq!(
+            parse_arguments(&arguments("--bag")),
+            Err(String::from("Expected a value after `--bag`"))
+        );
+        assert_eq!(
+            parse_arguments(&arguments("--bag red")),
+            Err(String::from("Expected `colour=amount` in bag, got `red`"))
+        );
+        assert_eq!(
+            parse_arguments(&arguments("--colour red")),
+            Err(String::from("Unknown option `--colour`"))
+        );
+    }
+
+    #[test]
+    fn parse_arguments_missing_file_test() {
+        let error = parse_arguments(&[String::from("--bags"), String::from("no-such-bags.txt")])
+            .unwrap_err();
+
+        assert!(
+            error.starts_with("Could not read bags from `no-such-bags.txt`: "),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn format_possible_games_test() {
+        let games = vec![
+            parse_game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"),
+            parse_game("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green"),
+            parse_game("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"),
+        ];
+        let bag = parse_bag(PUZZLE_BAG).unwrap();
+
+        assert_eq!(
+            format_possible_games(&games, &bag),
+            "blue=14,green=13,red=12: sum 6, ids 1, 5"
+        );
+    }
 }
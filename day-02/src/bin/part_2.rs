@@ -34,7 +34,15 @@
 */
 
 use common::read_input;
-use day_02_lib::parse_game;
+use day_02_lib::{parse_game, Game};
+
+// a game that never shows one of these still needs a bag with some of them,
+// and the power of its minimum bag is 0
+const PUZZLE_COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+fn minimum_power(game: &Game) -> i32 {
+    game.minimum_bag().power_over(&PUZZLE_COLOURS)
+}
 
 fn part_2() -> i32 {
     let file = read_input(2);
@@ -42,27 +50,7 @@ fn part_2() -> i32 {
     let mut sum: i32 = 0;
 
     for line in file.split("\n") {
-        let game = parse_game(&line);
-
-        let mut min_green_cubes = 0;
-        let mut min_blue_cubes = 0;
-        let mut min_red_cubes = 0;
-
-        for round in game.rounds.iter() {
-            if round.green_cubes > min_green_cubes {
-                min_green_cubes = round.green_cubes;
-            }
-
-            if round.blue_cubes > min_blue_cubes {
-                min_blue_cubes = round.blue_cubes;
-            }
-
-            if round.red_cubes > min_red_cubes {
-                min_red_cubes = round.red_cubes;
-            }
-        }
-
-        sum += min_green_cubes * min_blue_cubes * min_red_cubes;
+        sum += minimum_power(&parse_game(line));
     }
 
     sum
@@ -74,7 +62,22 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use crate::part_2;
+    use crate::{minimum_power, part_2};
+    use day_02_lib::parse_game;
+
+    #[test]
+    fn minimum_power_test() {
+        assert_eq!(
+            minimum_power(&parse_game(
+                "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+            )),
+            48
+        );
+        assert_eq!(
+            minimum_power(&parse_game("Game 1: 3 blue, 4 red; 6 blue")),
+            0
+        );
+    }
 
     #[test]
     fn part_2_test() {
@@ -0,0 +1,115 @@
+/*
+    Prints games drawn at random from a known bag, in the puzzle input's
+    format:
+    > simulate <bag> <games> <rounds per game> [--seed=N]
+
+    The bag is written like `red=12,green=13,blue=14`. Every round is a
+    random handful of the bag's cubes, and the same arguments always print
+    the same games.
+*/
+
+use std::env;
+use std::process;
+
+use day_02_lib::cube_set::CubeSet;
+use day_02_lib::parse_bag;
+use day_02_lib::simulation::{simulate_input, SimulationSettings};
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse::<T>()
+        .map_err(|_| format!("Expected a number for {name}, got `{value}`"))
+}
+
+fn parse_arguments(arguments: &[String]) -> Result<(CubeSet, SimulationSettings), String> {
+    let (positional, options): (Vec<&String>, Vec<&String>) = arguments
+        .iter()
+        .partition(|argument| !argument.starts_with("--"));
+
+    let [bag, game_count, rounds_per_game] = positional[..] else {
+        return Err(String::from(
+            "Expected `<bag> <games> <rounds per game> [--seed=N]`",
+        ));
+    };
+
+    let mut settings = SimulationSettings {
+        game_count: parse_number("the number of games", game_count)?,
+        rounds_per_game: parse_number("the rounds per game", rounds_per_game)?,
+        seed: 0,
+    };
+
+    for option in options {
+        match option.split_once('=') {
+            Some(("--seed", value)) => settings.seed = parse_number("--seed", value)?,
+            _ => return Err(format!("Unknown option `{option}`")),
+        }
+    }
+
+    let bag = parse_bag(bag).map_err(|error| error.to_string())?;
+
+    // checked here so that these are argument errors rather than the
+    // library's panics
+    if bag.cubes.is_empty() {
+        return Err(String::from("The bag needs at least one cube"));
+    }
+
+    if settings.rounds_per_game == 0 {
+        return Err(String::from("Every game needs at least one round"));
+    }
+
+    Ok((bag, settings))
+}
+
+fn main() {
+    let arguments: Vec<String> = env::args().skip(1).collect();
+
+    match parse_arguments(&arguments) {
+        Ok((bag, settings)) => println!("{}", simulate_input(&bag, &settings)),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arguments(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_arguments_test() {
+        assert_eq!(
+            parse_arguments(&arguments("red=12,green=13,blue=14 100 5 --seed=3")),
+            Ok((
+                CubeSet::from([("red", 12), ("green", 13), ("blue", 14)]),
+                SimulationSettings {
+                    game_count: 100,
+                    rounds_per_game: 5,
+                    seed: 3,
+                }
+            ))
+        );
+        assert_eq!(
+            parse_arguments(&arguments("red=12 many 5")),
+            Err(String::from(
+                "Expected a number for the number of games, got `many`"
+            ))
+        );
+        assert_eq!(
+            parse_arguments(&arguments("red 1 1")),
+            Err(String::from("Expected `colour=amount` in bag, got `red`"))
+        );
+        assert_eq!(
+            parse_arguments(&arguments("red=1 2 0")),
+            Err(String::from("Every game needs at least one round"))
+        );
+        assert_eq!(
+            parse_arguments(&arguments("red=0 2 1")),
+            Err(String::from("The bag needs at least one cube"))
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+use std::ops::Add;
+
+// how many cubes there are of each colour, where a colour that is not listed
+// has no cubes. A count that comes to zero is left out, so that `{red: 0}`
+// and `{}` are the same set
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CubeSet {
+    pub cubes: BTreeMap<String, i32>,
+}
+
+impl CubeSet {
+    pub fn new() -> CubeSet {
+        CubeSet {
+            cubes: BTreeMap::new(),
+        }
+    }
+
+    pub fn add_cubes(&mut self, colour: &str, amount: i32) {
+        let count = self.count(colour) + amount;
+
+        if count == 0 {
+            self.cubes.remove(colour);
+        } else {
+            self.cubes.insert(colour.to_string(), count);
+        }
+    }
+
+    pub fn count(&self, colour: &str) -> i32 {
+        self.cubes.get(colour).copied().unwrap_or(0)
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        self.cubes.keys().map(String::as_str)
+    }
+
+    // the larger count of every colour in either set
+    pub fn max(&self, other: &CubeSet) -> CubeSet {
+        let mut result = self.clone();
+
+        for (colour, amount) in other.cubes.iter() {
+            let count = result.cubes.entry(colour.clone()).or_insert(*amount);
+            *count = (*count).max(*amount);
+        }
+
+        result
+    }
+
+    // the smaller count of every colour in both sets
+    pub fn min(&self, other: &CubeSet) -> CubeSet {
+        let mut result = CubeSet::new();
+
+        for (colour, amount) in self.cubes.iter() {
+            if let Some(other_amount) = other.cubes.get(colour) {
+                result.add_cubes(colour, (*amount).min(*other_amount));
+            }
+        }
+
+        result
+    }
+
+    pub fn fits_within(&self, bag: &CubeSet) -> bool {
+        self.cubes
+            .iter()
+            .all(|(colour, amount)| *amount <= bag.count(colour))
+    }
+
+    // the counts of the given colours multiplied together, so that a colour
+    // with no cubes makes the power 0
+    pub fn power_over(&self, colours: &[&str]) -> i32 {
+        colours.iter().map(|colour| self.count(colour)).product()
+    }
+}
+
+impl Add<&CubeSet> for &CubeSet {
+    type Output = CubeSet;
+
+    fn add(self, other: &CubeSet) -> CubeSet {
+        let mut result = self.clone();
+
+        for (colour, amount) in other.cubes.iter() {
+            result.add_cubes(colour, *amount);
+        }
+
+        result
+    }
+}
+
+impl Add for CubeSet {
+    type Output = CubeSet;
+
+    fn add(self, other: CubeSet) -> CubeSet {
+        &self + &other
+    }
+}
+
+impl<const N: usize> From<[(&str, i32); N]> for CubeSet {
+    fn from(cubes: [(&str, i32); N]) -> CubeSet {
+        let mut cube_set = CubeSet::new();
+
+        for (colour, amount) in cubes {
+            cube_set.add_cubes(colour, amount);
+        }
+
+        cube_set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cube_set_test() {
+        let mut cube_set = CubeSet::from([("blue", 3), ("red", 4)]);
+        cube_set.add_cubes("gold", 2);
+        cube_set.add_cubes("blue", 1);
+
+        assert_eq!(cube_set.count("blue"), 4);
+        assert_eq!(cube_set.count("red"), 4);
+        assert_eq!(cube_set.count("gold"), 2);
+        assert_eq!(cube_set.count("green"), 0);
+        assert_eq!(
+            cube_set.colours().collect::<Vec<&str>>(),
+            vec!["blue", "gold", "red"]
+        );
+        assert_eq!(CubeSet::new(), CubeSet::default());
+    }
+
+    #[test]
+    fn zero_counts_test() {
+        let mut cube_set = CubeSet::from([("red", 0), ("blue", 2)]);
+
+        assert_eq!(cube_set, CubeSet::from([("blue", 2)]));
+        assert_eq!(cube_set.colours().collect::<Vec<&str>>(), vec!["blue"]);
+
+        cube_set.add_cubes("blue", -2);
+
+        assert_eq!(cube_set, CubeSet::new());
+        assert_eq!(
+            CubeSet::from([("red", 0)]).max(&CubeSet::new()),
+            CubeSet::from([("red", 0)]).min(&CubeSet::from([("red", 3)]))
+        );
+    }
+
+    #[test]
+    fn max_min_test() {
+        let first = CubeSet::from([("blue", 3), ("red", 4)]);
+        let second = CubeSet::from([("red", 1), ("green", 2), ("blue", 6)]);
+
+        assert_eq!(
+            first.max(&second),
+            CubeSet::from([("blue", 6), ("red", 4), ("green", 2)])
+        );
+        assert_eq!(first.min(&second), CubeSet::from([("blue", 3), ("red", 1)]));
+        assert_eq!(first.max(&CubeSet::new()), first);
+        assert_eq!(first.min(&CubeSet::new()), CubeSet::new());
+    }
+
+    #[test]
+    fn fits_within_test() {
+        let bag = CubeSet::from([("red", 12), ("green", 13), ("blue", 14)]);
+
+        assert!(CubeSet::from([("red", 12), ("blue", 1)]).fits_within(&bag));
+        assert!(CubeSet::new().fits_within(&bag));
+        assert!(!CubeSet::from([("red", 13)]).fits_within(&bag));
+        assert!(!CubeSet::from([("gold", 1)]).fits_within(&bag));
+        assert!(CubeSet::from([("gold", 0)]).fits_within(&bag));
+    }
+
+    #[test]
+    fn power_over_test() {
+        let colours = ["red", "green", "blue"];
+
+        assert_eq!(
+            CubeSet::from([("red", 4), ("green", 2), ("blue", 6)]).power_over(&colours),
+            48
+        );
+        assert_eq!(
+            CubeSet::from([("red", 4), ("gold", 2), ("blue", 6)]).power_over(&colours),
+            0
+        );
+        assert_eq!(
+            CubeSet::from([("red", 20), ("blue", 0)]).power_over(&["red", "blue"]),
+            0
+        );
+        assert_eq!(CubeSet::new().power_over(&colours), 0);
+        assert_eq!(CubeSet::from([("gold", 3)]).power_over(&["gold"]), 3);
+    }
+
+    #[test]
+    fn add_test() {
+        let first = CubeSet::from([("blue", 3), ("red", 4)]);
+        let second = CubeSet::from([("red", 1), ("green", 2)]);
+
+        assert_eq!(
+            &first + &second,
+            CubeSet::from([("blue", 3), ("red", 5), ("green", 2)])
+        );
+        assert_eq!(first.clone() + CubeSet::new(), first);
+    }
+}
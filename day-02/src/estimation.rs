@@ -0,0 +1,436 @@
+use std::collections::BTreeMap;
+
+use crate::cube_set::CubeSet;
+use crate::Game;
+
+// for a 95% confidence interval
+const Z: f64 = 1.96;
+
+// every round is a handful of cubes drawn from the bag and put back
+// afterwards. Within a round they are drawn either one at a time and put
+// back straight away, or all at once
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DrawModel {
+    WithReplacement,
+    WithoutReplacement,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BagEstimate {
+    pub bag: CubeSet,
+    pub log_likelihood: f64,
+}
+
+// the share of the bag a colour makes up, with a 95% Wilson interval
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShareInterval {
+    pub share: f64,
+    pub low: f64,
+    pub high: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameEstimate {
+    pub game_id: i32,
+    pub cubes_drawn: i32,
+    pub shares: BTreeMap<String, ShareInterval>,
+    pub with_replacement: BagEstimate,
+    pub without_replacement: BagEstimate,
+}
+
+// the games taken as draws from one and the same bag, and how many of them
+// have intervals containing every pooled share
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfidenceSummary {
+    pub games: usize,
+    pub cubes_drawn: i32,
+    pub shares: BTreeMap<String, ShareInterval>,
+    pub consistent_games: usize,
+}
+
+fn ln_factorial(n: i32) -> f64 {
+    (2..=n).map(|i| (i as f64).ln()).sum()
+}
+
+fn ln_choose(n: i32, k: i32) -> f64 {
+    if k < 0 || k > n {
+        return f64::NEG_INFINITY;
+    }
+
+    (1..=k).map(|i| ((n - k + i) as f64 / i as f64).ln()).sum()
+}
+
+fn round_size(round: &CubeSet) -> i32 {
+    round.cubes.values().sum()
+}
+
+fn drawn_per_colour(games: &[&Game]) -> CubeSet {
+    games
+        .iter()
+        .flat_map(|game| game.rounds.iter())
+        .fold(CubeSet::new(), |drawn, round| &drawn + &round.cubes)
+}
+
+pub fn log_likelihood(game: &Game, bag: &CubeSet, model: DrawModel) -> f64 {
+    let total = round_size(bag);
+    let mut log_likelihood = 0.0;
+
+    for round in game.rounds.iter() {
+        let size = round_size(&round.cubes);
+
+        log_likelihood += match model {
+            DrawModel::WithReplacement => {
+                let mut log_probability = ln_factorial(size);
+
+                for (colour, amount) in round.cubes.cubes.iter() {
+                    if *amount > 0 {
+                        let share = bag.count(colour) as f64 / total as f64;
+                        log_probability += *amount as f64 * share.ln() - ln_factorial(*amount);
+                    }
+                }
+
+                log_probability
+            }
+            DrawModel::WithoutReplacement => {
+                if size > total {
+                    return f64::NEG_INFINITY;
+                }
+
+                let mut log_probability = -ln_choose(total, size);
+
+                for (colour, amount) in round.cubes.cubes.iter() {
+                    log_probability += ln_choose(bag.count(colour), *amount);
+                }
+
+                log_probability
+            }
+        };
+    }
+
+    log_likelihood
+}
+
+// how much one more cube of the colour adds to the part of the log
+// likelihood that does not depend on the size of the bag
+fn marginal_gain(game: &Game, colour: &str, count: i32, model: DrawModel) -> f64 {
+    game.rounds
+        .iter()
+        .map(|round| round.cubes.count(colour))
+        .filter(|amount| *amount > 0)
+        .map(|amount| match model {
+            DrawModel::WithReplacement => amount as f64 * ((count + 1) as f64 / count as f64).ln(),
+            DrawModel::WithoutReplacement => {
+                ((count + 1) as f64 / (count + 1 - amount) as f64).ln()
+            }
+        })
+        .sum()
+}
+
+// the bag of at most `max_total` cubes, and no fewer of any colour than the
+// game's minimum bag, that makes the game most likely, the smallest one
+// winning ties. Any smaller bag could not have produced the game, so when the
+// minimum bag alone holds more than `max_total` cubes it is returned as it is.
+// For a given number of cubes both likelihoods split into a concave part per
+// colour, so adding cubes one at a time where they help the most gives the
+// best bag of every size along the way
+pub fn estimate_bag(game: &Game, model: DrawModel, max_total: i32) -> BagEstimate {
+    let mut bag = game.minimum_bag();
+    let mut best = BagEstimate {
+        bag: bag.clone(),
+        log_likelihood: log_likelihood(game, &bag, model),
+    };
+
+    let colours: Vec<String> = bag.colours().map(String::from).collect();
+
+    if colours.is_empty() {
+        return best;
+    }
+
+    for _ in round_size(&bag)..max_total {
+        let colour = colours
+            .iter()
+            .max_by(|first, second| {
+                let first_gain = marginal_gain(game, first, bag.count(first), model);
+                let second_gain = marginal_gain(game, second, bag.count(second), model);
+
+                first_gain.total_cmp(&second_gain)
+            })
+            .unwrap();
+
+        bag.add_cubes(colour, 1);
+
+        let log_likelihood = log_likelihood(game, &bag, model);
+
+        if log_likelihood > best.log_likelihood + 1e-9 {
+            best = BagEstimate {
+                bag: bag.clone(),
+                log_likelihood,
+            };
+        }
+    }
+
+    best
+}
+
+fn share_interval(amount: i32, draws: i32) -> ShareInterval {
+    let draws = draws as f64;
+    let share = amount as f64 / draws;
+
+    let denominator = 1.0 + Z * Z / draws;
+    let centre = (share + Z * Z / (2.0 * draws)) / denominator;
+    let half_width =
+        Z * (share * (1.0 - share) / draws + Z * Z / (4.0 * draws * draws)).sqrt() / denominator;
+
+    ShareInterval {
+        share,
+        low: (centre - half_width).max(0.0),
+        high: (centre + half_width).min(1.0),
+    }
+}
+
+// the shares that make the drawn cubes most likely, which are the same for
+// both models when the bag can be of any size
+fn shares(drawn: &CubeSet) -> BTreeMap<String, ShareInterval> {
+    let draws = round_size(drawn);
+
+    drawn
+        .cubes
+        .iter()
+        .filter(|_| draws > 0)
+        .map(|(colour, amount)| (colour.clone(), share_interval(*amount, draws)))
+        .collect()
+}
+
+pub fn estimate_game(game: &Game, max_total: i32) -> GameEstimate {
+    let drawn = drawn_per_colour(&[game]);
+
+    GameEstimate {
+        game_id: game.id,
+        cubes_drawn: round_size(&drawn),
+        shares: shares(&drawn),
+        with_replacement: estimate_bag(game, DrawModel::WithReplacement, max_total),
+        without_replacement: estimate_bag(game, DrawModel::WithoutReplacement, max_total),
+    }
+}
+
+pub fn estimate_games(games: &[Game], max_total: i32) -> (Vec<GameEstimate>, ConfidenceSummary) {
+    let estimates: Vec<GameEstimate> = games
+        .iter()
+        .map(|game| estimate_game(game, max_total))
+        .collect();
+
+    let drawn = drawn_per_colour(&games.iter().collect::<Vec<&Game>>());
+    let pooled_shares = shares(&drawn);
+
+    let consistent_games = estimates
+        .iter()
+        .filter(|estimate| {
+            pooled_shares.iter().all(|(colour, pooled)| {
+                let interval = estimate
+                    .shares
+                    .get(colour)
+                    .copied()
+                    .unwrap_or_else(|| share_interval(0, estimate.cubes_drawn.max(1)));
+
+                interval.low <= pooled.share && pooled.share <= interval.high
+            })
+        })
+        .count();
+
+    let summary = ConfidenceSummary {
+        games: games.len(),
+        cubes_drawn: round_size(&drawn),
+        shares: pooled_shares,
+        consistent_games,
+    };
+
+    (estimates, summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{example_games, parse_game};
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn log_likelihood_test() {
+        let game = parse_game("Game 9: 3 blue, 2 red");
+        let bag = CubeSet::from([("blue", 3), ("red", 2)]);
+
+        assert_close(
+            log_likelihood(&game, &bag, DrawModel::WithReplacement),
+            (10.0 * 0.6_f64.powi(3) * 0.4_f64.powi(2)).ln(),
+        );
+        assert_close(
+            log_likelihood(&game, &bag, DrawModel::WithoutReplacement),
+            0.0,
+        );
+        assert_close(
+            log_likelihood(
+                &game,
+                &CubeSet::from([("blue", 4), ("red", 2)]),
+                DrawModel::WithoutReplacement,
+            ),
+            (2.0 / 3.0_f64).ln(),
+        );
+        assert_eq!(
+            log_likelihood(
+                &game,
+                &CubeSet::from([("blue", 2), ("red", 2)]),
+                DrawModel::WithoutReplacement
+            ),
+            f64::NEG_INFINITY
+        );
+    }
+
+    #[test]
+    fn estimate_bag_single_round_test() {
+        let game = parse_game("Game 9: 3 blue, 2 red");
+
+        for model in [DrawModel::WithReplacement, DrawModel::WithoutReplacement] {
+            assert_eq!(
+                estimate_bag(&game, model, 50).bag,
+                CubeSet::from([("blue", 3), ("red", 2)])
+            );
+        }
+    }
+
+    #[test]
+    fn estimate_bag_minimum_bag_over_max_total_test() {
+        let game = parse_game("Game 9: 3 blue, 2 red; 1 blue, 1 green");
+        let minimum_bag = CubeSet::from([("blue", 3), ("green", 1), ("red", 2)]);
+
+        for model in [DrawModel::WithReplacement, DrawModel::WithoutReplacement] {
+            for max_total in [0, 5] {
+                let estimate = estimate_bag(&game, model, max_total);
+
+                assert_eq!(estimate.bag, minimum_bag);
+                assert_close(
+                    estimate.log_likelihood,
+                    log_likelihood(&game, &minimum_bag, model),
+                );
+            }
+        }
+    }
+
+    // compares the estimate with every bag from the minimum bag up to
+    // `max_total` cubes
+    #[test]
+    fn estimate_bag_matches_search_test() {
+        let max_total = 40;
+
+        for game in example_games() {
+            let minimum_bag = game.minimum_bag();
+            let (blue, green, red) = (
+                minimum_bag.count("blue"),
+                minimum_bag.count("green"),
+                minimum_bag.count("red"),
+            );
+
+            for model in [DrawModel::WithReplacement, DrawModel::WithoutReplacement] {
+                let estimate = estimate_bag(&game, model, max_total);
+                let mut best = f64::NEG_INFINITY;
+
+                for blue in blue..=max_total {
+                    for green in green..=max_total - blue {
+                        for red in red..=max_total - blue - green {
+                            let bag =
+                                CubeSet::from([("blue", blue), ("green", green), ("red", red)]);
+                            best = best.max(log_likelihood(&game, &bag, model));
+                        }
+                    }
+                }
+
+                assert!(estimate.bag.fits_within(&CubeSet::from([
+                    ("blue", max_total),
+                    ("green", max_total),
+                    ("red", max_total)
+                ])));
+                assert!(minimum_bag.fits_within(&estimate.bag));
+                assert!(round_size(&estimate.bag) <= max_total);
+                assert_close(estimate.log_likelihood, best);
+            }
+        }
+    }
+
+    #[test]
+    fn estimate_game_test() {
+        let estimate = estimate_game(&example_games()[0], 50);
+
+        assert_eq!(estimate.game_id, 1);
+        assert_eq!(estimate.cubes_drawn, 18);
+        assert_close(estimate.shares["blue"].share, 0.5);
+        assert_close(estimate.shares["green"].share, 4.0 / 18.0);
+        assert_close(estimate.shares["red"].share, 5.0 / 18.0);
+
+        for interval in estimate.shares.values() {
+            assert!(0.0 <= interval.low && interval.low < interval.share);
+            assert!(interval.share < interval.high && interval.high <= 1.0);
+        }
+
+        // blue, green and red drawn 9, 4 and 5 times, in a bag of at least
+        // 6, 2 and 4
+        assert_eq!(
+            estimate.with_replacement.bag,
+            CubeSet::from([("blue", 9), ("green", 4), ("red", 5)])
+        );
+    }
+
+    #[test]
+    fn share_interval_test() {
+        let interval = share_interval(5, 10);
+
+        assert_close(interval.share, 0.5);
+        assert!((interval.low - 0.2366).abs() < 1e-4);
+        assert!((interval.high - 0.7634).abs() < 1e-4);
+
+        let interval = share_interval(0, 10);
+
+        assert_close(interval.low, 0.0);
+        assert!((interval.high - 0.2775).abs() < 1e-4);
+    }
+
+    #[test]
+    fn estimate_games_test() {
+        let games = example_games();
+        let (estimates, summary) = estimate_games(&games, 60);
+
+        assert_eq!(estimates.len(), 5);
+        assert_eq!(
+            estimates
+                .iter()
+                .map(|estimate| estimate.game_id)
+                .collect::<Vec<i32>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(summary.games, 5);
+        assert_eq!(summary.cubes_drawn, 159);
+        assert_close(
+            summary.shares.values().map(|interval| interval.share).sum(),
+            1.0,
+        );
+        assert_eq!(summary.consistent_games, 2);
+    }
+
+    #[test]
+    fn estimate_games_input_test() {
+        let games = crate::parse_games(&common::read_input(2)).unwrap();
+        let (estimates, summary) = estimate_games(&games, 100);
+
+        assert_eq!(estimates.len(), 100);
+        assert_eq!(summary.games, 100);
+
+        for (game, estimate) in games.iter().zip(estimates.iter()) {
+            assert!(game
+                .minimum_bag()
+                .fits_within(&estimate.with_replacement.bag));
+            assert!(game
+                .minimum_bag()
+                .fits_within(&estimate.without_replacement.bag));
+        }
+    }
+}
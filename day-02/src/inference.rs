@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::cube_set::CubeSet;
+use crate::{possible_game_ids, Game};
+
+// the counts a colour can have in a consistent bag, `None` meaning that any
+// count from `min` upwards works
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColourRange {
+    pub min: i32,
+    pub max: Option<i32>,
+}
+
+// every bag that makes exactly the claimed games possible. The ranges are
+// per colour, so not every combination of counts from them is consistent:
+// an impossible game only needs one colour to be over the limit, and which
+// one can differ from bag to bag
+#[derive(Clone, Debug, PartialEq)]
+pub struct BagRegion<'a> {
+    pub ranges: BTreeMap<String, ColourRange>,
+    games: &'a [Game],
+    possible_ids: Vec<i32>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BagInferenceError {
+    UnknownId(i32),
+    // the game is possible with the smallest bag that allows the claimed ones
+    NoConsistentBag { game_id: i32 },
+}
+
+impl BagRegion<'_> {
+    pub fn contains(&self, bag: &CubeSet) -> bool {
+        possible_game_ids(self.games, bag) == self.possible_ids
+    }
+}
+
+// the bag has to hold the minimum bag of every possible game. Any bag that
+// large or larger still allows an impossible game fitting in it, so each
+// impossible game needs a colour above that lower bound, and a colour is
+// capped only by the impossible games that have no other such colour
+pub fn infer_bag_region<'a>(
+    games: &'a [Game],
+    possible_ids: &[i32],
+) -> Result<BagRegion<'a>, BagInferenceError> {
+    if let Some(id) = possible_ids
+        .iter()
+        .find(|id| !games.iter().any(|game| game.id == **id))
+    {
+        return Err(BagInferenceError::UnknownId(*id));
+    }
+
+    let (possible, impossible): (Vec<&Game>, Vec<&Game>) = games
+        .iter()
+        .partition(|game| possible_ids.contains(&game.id));
+
+    let lower_bound = possible
+        .iter()
+        .fold(CubeSet::new(), |bag, game| bag.max(&game.minimum_bag()));
+
+    let mut ranges = BTreeMap::new();
+
+    for game in games.iter() {
+        for colour in game.minimum_bag().colours() {
+            ranges.insert(
+                colour.to_string(),
+                ColourRange {
+                    min: lower_bound.count(colour),
+                    max: None,
+                },
+            );
+        }
+    }
+
+    for game in impossible.iter() {
+        let minimum_bag = game.minimum_bag();
+        let over_lower_bound: Vec<(&str, i32)> = minimum_bag
+            .cubes
+            .iter()
+            .filter(|(colour, amount)| **amount > lower_bound.count(colour))
+            .map(|(colour, amount)| (colour.as_str(), *amount))
+            .collect();
+
+        match over_lower_bound.as_slice() {
+            [] => return Err(BagInferenceError::NoConsistentBag { game_id: game.id }),
+            [(colour, amount)] => {
+                let range = ranges.get_mut(*colour).unwrap();
+                range.max = Some(range.max.map_or(amount - 1, |max| max.min(amount - 1)));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(BagRegion {
+        ranges,
+        games,
+        possible_ids: possible.iter().map(|game| game.id).collect(),
+    })
+}
+
+impl fmt::Display for BagInferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BagInferenceError::UnknownId(id) => write!(f, "There is no game with id {id}"),
+            BagInferenceError::NoConsistentBag { game_id } => write!(
+                f,
+                "No bag allows exactly these games, as game {game_id} would also be possible"
+            ),
+        }
+    }
+}
+
+impl Error for BagInferenceError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{example_games, parse_bag};
+
+    fn range(min: i32, max: Option<i32>) -> ColourRange {
+        ColourRange { min, max }
+    }
+
+    #[test]
+    fn infer_bag_region_example_test() {
+        let games = example_games();
+        let region = infer_bag_region(&games, &[1, 2, 5]).unwrap();
+
+        assert_eq!(region.ranges["blue"], range(6, None));
+        assert_eq!(region.ranges["green"], range(3, None));
+        assert_eq!(region.ranges["red"], range(6, None));
+
+        assert!(region.contains(&parse_bag("red=12,green=13,blue=14").unwrap()));
+        assert!(region.contains(&parse_bag("red=6,green=3,blue=6").unwrap()));
+        assert!(region.contains(&parse_bag("red=13,green=100,blue=14").unwrap()));
+        assert!(!region.contains(&parse_bag("red=5,green=3,blue=6").unwrap()));
+        assert!(!region.contains(&parse_bag("red=14,green=3,blue=15").unwrap()));
+        assert!(!region.contains(&parse_bag("red=20,green=13,blue=6").unwrap()));
+    }
+
+    #[test]
+    fn infer_bag_region_capped_test() {
+        let games = example_games();
+        let region = infer_bag_region(&games, &[1, 2, 3, 5]).unwrap();
+
+        assert_eq!(region.ranges["blue"], range(6, Some(14)));
+        assert_eq!(region.ranges["green"], range(13, None));
+        assert_eq!(region.ranges["red"], range(20, None));
+
+        assert!(region.contains(&parse_bag("red=20,green=13,blue=14").unwrap()));
+        assert!(!region.contains(&parse_bag("red=20,green=13,blue=15").unwrap()));
+    }
+
+    #[test]
+    fn infer_bag_region_matches_contains_test() {
+        let games = example_games();
+
+        // every count in a range appears in some consistent bag, and none
+        // outside it does
+        for possible_ids in [&[1, 2, 5][..], &[1, 2, 3, 5], &[2, 5], &[1, 2, 3, 4, 5]] {
+            let region = infer_bag_region(&games, possible_ids).unwrap();
+
+            for (colour, colour_range) in region.ranges.iter() {
+                for count in 0..=25 {
+                    let mut bag = CubeSet::new();
+
+                    for (other_colour, other_range) in region.ranges.iter() {
+                        bag.add_cubes(other_colour, other_range.min);
+                    }
+
+                    bag.add_cubes(colour, count - bag.count(colour));
+
+                    let in_range = count >= colour_range.min
+                        && colour_range.max.is_none_or(|max| count <= max);
+
+                    assert_eq!(region.contains(&bag), in_range, "{colour}={count}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn infer_bag_region_error_test() {
+        let games = example_games();
+
+        assert_eq!(
+            infer_bag_region(&games, &[4]),
+            Err(BagInferenceError::NoConsistentBag { game_id: 1 })
+        );
+        assert_eq!(
+            infer_bag_region(&games, &[1, 7]),
+            Err(BagInferenceError::UnknownId(7))
+        );
+        assert_eq!(
+            BagInferenceError::NoConsistentBag { game_id: 1 }.to_string(),
+            "No bag allows exactly these games, as game 1 would also be possible"
+        );
+    }
+}
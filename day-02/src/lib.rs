@@ -1,87 +1,376 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
 use regex::Regex;
 
+pub mod cube_set;
+pub mod estimation;
+pub mod inference;
+pub mod simulation;
+pub mod smallest_bag;
+pub mod validation;
+
+use cube_set::CubeSet;
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Round {
-    pub green_cubes: i32,
-    pub blue_cubes: i32,
-    pub red_cubes: i32,
+    pub cubes: CubeSet,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Game {
     pub id: i32,
     pub rounds: Vec<Round>,
 }
 
-fn parse_game_id(string: &str) -> i32 {
-    let game_id_regex = Regex::new(r"Game\s(?<id>\d+)").unwrap();
-    let captures = game_id_regex.captures(string).unwrap();
-    let capture = captures.name("id").unwrap().as_str();
+impl Round {
+    pub fn green_cubes(&self) -> i32 {
+        self.cubes.count("green")
+    }
+
+    pub fn blue_cubes(&self) -> i32 {
+        self.cubes.count("blue")
+    }
+
+    pub fn red_cubes(&self) -> i32 {
+        self.cubes.count("red")
+    }
+}
 
-    capture.parse::<i32>().unwrap()
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameParseError {
+    MissingColon(String),
+    BadId(String),
+    OddTokenCount(String),
+    BadAmount(String),
+    UnknownColour(String),
 }
 
-pub fn parse_game(string: &str) -> Game {
-    let parts: Vec<&str> = string.split(":").collect();
-    let [game_vector, game_data] = match parts.as_slice() {
-        [gv, gd] => [gv.trim(), gd.trim()],
-        _ => panic!("Expected exactly two parts in the string"),
+#[derive(Clone, Debug, PartialEq)]
+pub enum BagParseError {
+    MissingEquals(String),
+    BadAmount(String),
+    UnknownColour(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineParseError<E = GameParseError> {
+    pub line_number: usize,
+    pub error: E,
+}
+
+impl Game {
+    // the fewest cubes of each colour that could have been in the bag
+    pub fn minimum_bag(&self) -> CubeSet {
+        self.rounds
+            .iter()
+            .fold(CubeSet::new(), |bag, round| bag.max(&round.cubes))
+    }
+
+    pub fn is_possible_with(&self, bag: &CubeSet) -> bool {
+        self.rounds.iter().all(|round| round.cubes.fits_within(bag))
+    }
+}
+
+fn parse_game_id(string: &str) -> Result<i32, GameParseError> {
+    let game_id_regex = Regex::new(r"^Game\s(?<id>\d+)$").unwrap();
+    let bad_id = || GameParseError::BadId(string.to_string());
+
+    let captures = game_id_regex.captures(string).ok_or_else(bad_id)?;
+    let capture = captures.name("id").ok_or_else(bad_id)?.as_str();
+
+    capture.parse::<i32>().map_err(|_| bad_id())
+}
+
+fn parse_amount(string: &str) -> Result<i32, GameParseError> {
+    let bad_amount = || GameParseError::BadAmount(string.to_string());
+
+    if !string.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(bad_amount());
+    }
+
+    string.parse::<i32>().map_err(|_| bad_amount())
+}
+
+// any colour is accepted, as long as it is a word
+fn parse_colour(string: &str) -> Result<&str, GameParseError> {
+    if string.is_empty() || !string.chars().all(char::is_alphabetic) {
+        return Err(GameParseError::UnknownColour(string.to_string()));
+    }
+
+    Ok(string)
+}
+
+// the amount and colour of every cube count in the round, in order. A round
+// with nothing in it is empty rather than malformed
+fn parse_round_cubes(round: &str) -> Result<Vec<(i32, String)>, GameParseError> {
+    let round = round.trim();
+
+    if round.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let round_without_commas = round.replace(",", "");
+    let round_vector: Vec<&str> = round_without_commas.split(" ").collect();
+
+    if !round_vector.len().is_multiple_of(2) {
+        return Err(GameParseError::OddTokenCount(round.to_string()));
+    }
+
+    let mut cubes = Vec::new();
+
+    for half_i in 0..round_vector.len() / 2 {
+        let amount = parse_amount(round_vector[half_i * 2])?;
+        let colour = parse_colour(round_vector[half_i * 2 + 1])?;
+
+        cubes.push((amount, colour.to_string()));
+    }
+
+    Ok(cubes)
+}
+
+fn try_parse_round(round: &str) -> Result<Round, GameParseError> {
+    let mut current_round = Round {
+        cubes: CubeSet::new(),
+    };
+
+    for (amount, colour) in parse_round_cubes(round)? {
+        current_round.cubes.add_cubes(&colour, amount);
+    }
+
+    Ok(current_round)
+}
+
+// the game id and the text of every round, where nothing at all after the
+// colon means no rounds and `Game 1: ` is a single empty round
+fn split_game(string: &str) -> Result<(i32, Vec<&str>), GameParseError> {
+    let (game_vector, game_data) = string
+        .split_once(':')
+        .ok_or_else(|| GameParseError::MissingColon(string.to_string()))?;
+
+    let rounds = match game_data {
+        "" => Vec::new(),
+        _ => game_data.split(";").collect(),
     };
 
+    Ok((parse_game_id(game_vector.trim())?, rounds))
+}
+
+pub fn try_parse_game(string: &str) -> Result<Game, GameParseError> {
+    let (id, rounds) = split_game(string)?;
+
     let mut game = Game {
-        id: parse_game_id(game_vector),
+        id,
         rounds: Vec::new(),
     };
 
-    for round in game_data.replace(",", "").split(";") {
-        let round_vector: Vec<&str> = round.trim().split(" ").collect();
+    for round in rounds {
+        game.rounds.push(try_parse_round(round)?);
+    }
+
+    Ok(game)
+}
+
+pub fn parse_game(string: &str) -> Game {
+    match try_parse_game(string) {
+        Ok(game) => game,
+        Err(error) => panic!("{error}"),
+    }
+}
+
+// a bag is written like `red=12,green=13,blue=14`
+pub fn parse_bag(string: &str) -> Result<CubeSet, BagParseError> {
+    let mut bag = CubeSet::new();
+
+    for cubes in string.split(',').map(str::trim) {
+        let (colour, amount) = cubes
+            .split_once('=')
+            .ok_or_else(|| BagParseError::MissingEquals(cubes.to_string()))?;
+
+        let amount = parse_amount(amount.trim())
+            .map_err(|_| BagParseError::BadAmount(amount.trim().to_string()))?;
+        let colour = parse_colour(colour.trim())
+            .map_err(|_| BagParseError::UnknownColour(colour.trim().to_string()))?;
 
-        if round_vector.len() % 2 != 0 {
-            panic!("Round is not of even length: {round}");
+        bag.add_cubes(colour, amount);
+    }
+
+    Ok(bag)
+}
+
+// one bag per line, blank lines and lines starting with `#` are skipped
+pub fn parse_bags(input: &str) -> Result<Vec<CubeSet>, Vec<LineParseError<BagParseError>>> {
+    let mut bags = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
         }
 
-        let mut current_round = Round {
-            green_cubes: 0,
-            blue_cubes: 0,
-            red_cubes: 0,
-        };
+        match parse_bag(line) {
+            Ok(bag) => bags.push(bag),
+            Err(error) => errors.push(LineParseError {
+                line_number: index + 1,
+                error,
+            }),
+        }
+    }
 
-        for half_i in 0..round_vector.len() / 2 {
-            let first_element = round_vector[half_i * 2];
-            let second_element = round_vector[half_i * 2 + 1];
-
-            let amount = first_element
-                .parse::<i32>()
-                .expect("Could not parse amount as i32");
-
-            match second_element.get(0..1) {
-                Some("g") => {
-                    current_round.green_cubes += amount;
-                }
-                Some("b") => {
-                    current_round.blue_cubes += amount;
-                }
-                Some("r") => {
-                    current_round.red_cubes += amount;
-                }
-                _ => panic!("Second element does not start with 'g', 'b', or 'r'"),
-            };
+    if errors.is_empty() {
+        Ok(bags)
+    } else {
+        Err(errors)
+    }
+}
+
+pub fn possible_game_ids(games: &[Game], bag: &CubeSet) -> Vec<i32> {
+    games
+        .iter()
+        .filter(|game| game.is_possible_with(bag))
+        .map(|game| game.id)
+        .collect()
+}
+
+// every line is parsed, so all of the errors are reported at once
+pub fn parse_games(input: &str) -> Result<Vec<Game>, Vec<LineParseError>> {
+    let mut games = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        match try_parse_game(line) {
+            Ok(game) => games.push(game),
+            Err(error) => errors.push(LineParseError {
+                line_number: index + 1,
+                error,
+            }),
         }
+    }
+
+    if errors.is_empty() {
+        Ok(games)
+    } else {
+        Err(errors)
+    }
+}
 
-        game.rounds.push(current_round);
+impl FromStr for Round {
+    type Err = GameParseError;
+
+    fn from_str(string: &str) -> Result<Round, GameParseError> {
+        try_parse_round(string)
     }
+}
 
-    game
+impl FromStr for Game {
+    type Err = GameParseError;
+
+    fn from_str(string: &str) -> Result<Game, GameParseError> {
+        try_parse_game(string)
+    }
+}
+
+// colours are written in alphabetical order, like "3 blue, 4 red"
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cubes: Vec<String> = self
+            .cubes
+            .cubes
+            .iter()
+            .map(|(colour, amount)| format!("{amount} {colour}"))
+            .collect();
+
+        write!(f, "{}", cubes.join(", "))
+    }
+}
+
+// every round is written after a space, so a game without rounds is
+// `Game 1:` and one with a single empty round is `Game 1: `
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rounds: Vec<String> = self
+            .rounds
+            .iter()
+            .map(|round| format!(" {round}"))
+            .collect();
+
+        write!(f, "Game {}:{}", self.id, rounds.join(";"))
+    }
+}
+
+impl fmt::Display for GameParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameParseError::MissingColon(text) => {
+                write!(f, "Expected a `:` after the game id: `{text}`")
+            }
+            GameParseError::BadId(text) => write!(f, "Expected `Game <id>`, got `{text}`"),
+            GameParseError::OddTokenCount(text) => {
+                write!(f, "Round is not of even length: `{text}`")
+            }
+            GameParseError::BadAmount(text) => write!(f, "Could not parse amount `{text}`"),
+            GameParseError::UnknownColour(text) => write!(f, "Unknown colour `{text}`"),
+        }
+    }
+}
+
+impl Error for GameParseError {}
+
+impl fmt::Display for BagParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BagParseError::MissingEquals(text) => {
+                write!(f, "Expected `colour=amount` in bag, got `{text}`")
+            }
+            BagParseError::BadAmount(text) => write!(f, "Could not parse amount `{text}`"),
+            BagParseError::UnknownColour(text) => write!(f, "Unknown colour `{text}`"),
+        }
+    }
+}
+
+impl Error for BagParseError {}
+
+impl<E: fmt::Display> fmt::Display for LineParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Line {}: {}", self.line_number, self.error)
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> Error for LineParseError<E> {}
+
+// the five games from the puzzle's example, for the tests of every module
+#[cfg(test)]
+pub(crate) fn example_games() -> Vec<Game> {
+    parse_games(
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+    )
+    .unwrap()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::random::Xorshift;
 
     #[test]
     fn parse_game_id_test() {
-        assert_eq!(parse_game_id("Game 1"), 1);
-        assert_eq!(parse_game_id("Game 29"), 29);
+        assert_eq!(parse_game_id("Game 1"), Ok(1));
+        assert_eq!(parse_game_id("Game 29"), Ok(29));
+        assert_eq!(
+            parse_game_id("Gme 3"),
+            Err(GameParseError::BadId(String::from("Gme 3")))
+        );
+        assert_eq!(
+            parse_game_id("Game 99999999999"),
+            Err(GameParseError::BadId(String::from("Game 99999999999")))
+        );
     }
 
     #[test]
@@ -91,19 +380,13 @@ mod tests {
             id: 1,
             rounds: vec![
                 Round {
-                    green_cubes: 0,
-                    blue_cubes: 3,
-                    red_cubes: 4,
+                    cubes: CubeSet::from([("blue", 3), ("red", 4)]),
                 },
                 Round {
-                    green_cubes: 2,
-                    blue_cubes: 6,
-                    red_cubes: 1,
+                    cubes: CubeSet::from([("blue", 6), ("red", 1), ("green", 2)]),
                 },
                 Round {
-                    green_cubes: 2,
-                    blue_cubes: 0,
-                    red_cubes: 0,
+                    cubes: CubeSet::from([("green", 2)]),
                 },
             ],
         };
@@ -118,23 +401,333 @@ mod tests {
             id: 2,
             rounds: vec![
                 Round {
-                    green_cubes: 2,
-                    blue_cubes: 1,
-                    red_cubes: 0,
+                    cubes: CubeSet::from([("blue", 1), ("green", 2)]),
                 },
                 Round {
-                    green_cubes: 3,
-                    blue_cubes: 4,
-                    red_cubes: 1,
+                    cubes: CubeSet::from([("blue", 4), ("red", 1), ("green", 3)]),
                 },
                 Round {
-                    green_cubes: 1,
-                    blue_cubes: 1,
-                    red_cubes: 0,
+                    cubes: CubeSet::from([("blue", 1), ("green", 1)]),
                 },
             ],
         };
 
         assert_eq!(parse_game(game_2_string), game_2);
     }
+
+    #[test]
+    fn parse_game_colours_test() {
+        let game = parse_game("Game 7: 2 gold, 1 green; 3 purple");
+
+        assert_eq!(
+            game.rounds,
+            vec![
+                Round {
+                    cubes: CubeSet::from([("gold", 2), ("green", 1)]),
+                },
+                Round {
+                    cubes: CubeSet::from([("purple", 3)]),
+                },
+            ]
+        );
+        assert_eq!(game.rounds[0].green_cubes(), 1);
+        assert_eq!(game.rounds[1].green_cubes(), 0);
+        assert_eq!(game.rounds[1].red_cubes(), 0);
+    }
+
+    #[test]
+    fn try_parse_game_test() {
+        let error = |string: &str| try_parse_game(string).unwrap_err();
+
+        assert_eq!(
+            error("Game 1 3 blue"),
+            GameParseError::MissingColon(String::from("Game 1 3 blue"))
+        );
+        assert_eq!(
+            error("Game one: 3 blue"),
+            GameParseError::BadId(String::from("Game one"))
+        );
+        assert_eq!(
+            error("Game 1: 3 blue; 4 red, 2"),
+            GameParseError::OddTokenCount(String::from("4 red, 2"))
+        );
+        assert_eq!(
+            error("Game 1: 3 blue, x red"),
+            GameParseError::BadAmount(String::from("x"))
+        );
+        assert_eq!(
+            error("Game 1: -3 blue"),
+            GameParseError::BadAmount(String::from("-3"))
+        );
+        assert_eq!(
+            error("Game 1: 3 blue, 4 4"),
+            GameParseError::UnknownColour(String::from("4"))
+        );
+        assert_eq!(
+            try_parse_game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"),
+            Ok(parse_game(
+                "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+            ))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not parse amount `x`")]
+    fn parse_game_panic_test() {
+        parse_game("Game 1: x blue");
+    }
+
+    #[test]
+    fn parse_games_test() {
+        let games = parse_games("Game 1: 3 blue\nGame 2: 1 red, 2 green\n").unwrap();
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[1].id, 2);
+
+        assert_eq!(
+            parse_games("Game 1: 3 blue\nGame 2 1 red\nGame 3: 2 green\nGame 4: 2 4"),
+            Err(vec![
+                LineParseError {
+                    line_number: 2,
+                    error: GameParseError::MissingColon(String::from("Game 2 1 red")),
+                },
+                LineParseError {
+                    line_number: 4,
+                    error: GameParseError::UnknownColour(String::from("4")),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn line_parse_error_display_test() {
+        let error = LineParseError {
+            line_number: 4,
+            error: GameParseError::UnknownColour(String::from("4")),
+        };
+
+        assert_eq!(error.to_string(), "Line 4: Unknown colour `4`");
+    }
+
+    #[test]
+    fn parse_games_input_test() {
+        let games = parse_games(&common::read_input(2)).unwrap();
+
+        assert_eq!(games.len(), 100);
+    }
+
+    #[test]
+    fn display_test() {
+        let game = parse_game("Game 1: 4 red, 3 blue; 1 red, 2 green, 6 blue; 2 green");
+
+        assert_eq!(game.rounds[0].to_string(), "3 blue, 4 red");
+        assert_eq!(
+            game.to_string(),
+            "Game 1: 3 blue, 4 red; 6 blue, 2 green, 1 red; 2 green"
+        );
+    }
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(
+            "3 blue, 4 red".parse::<Round>(),
+            Ok(Round {
+                cubes: CubeSet::from([("blue", 3), ("red", 4)]),
+            })
+        );
+        assert_eq!(
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green".parse::<Game>(),
+            Ok(parse_game(
+                "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
+            ))
+        );
+        assert_eq!(
+            "3 blue, 4".parse::<Round>(),
+            Err(GameParseError::OddTokenCount(String::from("3 blue, 4")))
+        );
+    }
+
+    #[test]
+    fn zero_amounts_test() {
+        let game = parse_game("Game 1: 0 red, 3 blue; 0 green");
+
+        assert_eq!(game, parse_game("Game 1: 3 blue; "));
+        assert_eq!(game.to_string(), "Game 1: 3 blue; ");
+        assert_eq!(game.minimum_bag(), CubeSet::from([("blue", 3)]));
+    }
+
+    #[test]
+    fn empty_rounds_test() {
+        let empty_round = Round {
+            cubes: CubeSet::new(),
+        };
+        let no_rounds = Game {
+            id: 1,
+            rounds: Vec::new(),
+        };
+        let one_empty_round = Game {
+            id: 1,
+            rounds: vec![empty_round.clone()],
+        };
+        let empty_rounds_around = Game {
+            id: 1,
+            rounds: vec![
+                empty_round.clone(),
+                "3 blue".parse::<Round>().unwrap(),
+                empty_round.clone(),
+            ],
+        };
+
+        assert_eq!(empty_round.to_string(), "");
+        assert_eq!("".parse::<Round>(), Ok(empty_round));
+
+        assert_eq!(no_rounds.to_string(), "Game 1:");
+        assert_eq!(one_empty_round.to_string(), "Game 1: ");
+        assert_eq!(empty_rounds_around.to_string(), "Game 1: ; 3 blue; ");
+
+        for game in [no_rounds, one_empty_round, empty_rounds_around] {
+            assert_eq!(game.to_string().parse::<Game>(), Ok(game));
+        }
+    }
+
+    fn generate_game(random: &mut Xorshift) -> Game {
+        let colours = ["red", "green", "blue", "gold", "purple", "é", "Teal"];
+
+        let mut game = Game {
+            id: random.up_to(999) as i32,
+            rounds: Vec::new(),
+        };
+
+        // games without rounds and rounds without cubes included
+        for _ in 0..random.up_to(5) {
+            let mut round = Round {
+                cubes: CubeSet::new(),
+            };
+
+            for _ in 0..random.up_to(4) {
+                let colour = colours[random.up_to(colours.len() - 1)];
+                round.cubes.add_cubes(colour, random.up_to(29) as i32);
+            }
+
+            game.rounds.push(round);
+        }
+
+        game
+    }
+
+    #[test]
+    fn display_from_str_round_trip_test() {
+        let mut random = Xorshift::new(0x2023_1202);
+
+        for _ in 0..1000 {
+            let game = generate_game(&mut random);
+
+            assert_eq!(game.to_string().parse::<Game>(), Ok(game.clone()));
+        }
+    }
+
+    #[test]
+    fn display_from_str_round_trip_input_test() {
+        for game in parse_games(&common::read_input(2)).unwrap() {
+            assert_eq!(game.to_string().parse::<Game>(), Ok(game));
+        }
+    }
+
+    #[test]
+    fn minimum_bag_test() {
+        let game = parse_game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+
+        assert_eq!(
+            game.minimum_bag(),
+            CubeSet::from([("blue", 6), ("green", 2), ("red", 4)])
+        );
+        assert_eq!(game.minimum_bag().power_over(&["red", "green", "blue"]), 48);
+        assert_eq!(
+            parse_game("Game 1: 3 blue, 4 red; 6 blue")
+                .minimum_bag()
+                .power_over(&["red", "green", "blue"]),
+            0
+        );
+    }
+
+    #[test]
+    fn is_possible_with_test() {
+        let bag = CubeSet::from([("red", 12), ("green", 13), ("blue", 14)]);
+
+        assert!(
+            parse_game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")
+                .is_possible_with(&bag)
+        );
+        assert!(!parse_game(
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red"
+        )
+        .is_possible_with(&bag));
+        assert!(!parse_game("Game 6: 1 gold").is_possible_with(&bag));
+    }
+
+    #[test]
+    fn parse_bag_test() {
+        assert_eq!(
+            parse_bag("red=12,green=13,blue=14"),
+            Ok(CubeSet::from([("red", 12), ("green", 13), ("blue", 14)]))
+        );
+        assert_eq!(
+            parse_bag(" gold = 2 , red=1"),
+            Ok(CubeSet::from([("gold", 2), ("red", 1)]))
+        );
+        assert_eq!(
+            parse_bag("red=12,green"),
+            Err(BagParseError::MissingEquals(String::from("green")))
+        );
+        assert_eq!(
+            parse_bag("red=many"),
+            Err(BagParseError::BadAmount(String::from("many")))
+        );
+        assert_eq!(
+            parse_bag("r3d=1"),
+            Err(BagParseError::UnknownColour(String::from("r3d")))
+        );
+    }
+
+    #[test]
+    fn parse_bags_test() {
+        assert_eq!(
+            parse_bags("# puzzle\nred=12,green=13,blue=14\n\nred=20"),
+            Ok(vec![
+                CubeSet::from([("red", 12), ("green", 13), ("blue", 14)]),
+                CubeSet::from([("red", 20)])
+            ])
+        );
+
+        let errors = parse_bags("red=1\nred\nblue=x").unwrap_err();
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+            vec![
+                "Line 2: Expected `colour=amount` in bag, got `red`",
+                "Line 3: Could not parse amount `x`"
+            ]
+        );
+    }
+
+    #[test]
+    fn possible_game_ids_test() {
+        let games = example_games();
+
+        assert_eq!(
+            possible_game_ids(&games, &parse_bag("red=12,green=13,blue=14").unwrap()),
+            vec![1, 2, 5]
+        );
+        assert_eq!(
+            possible_game_ids(&games, &parse_bag("red=20,green=13,blue=15").unwrap()),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            possible_game_ids(&games, &CubeSet::new()),
+            Vec::<i32>::new()
+        );
+    }
 }
@@ -0,0 +1,147 @@
+use std::iter;
+
+use common::random::Xorshift;
+
+use crate::cube_set::CubeSet;
+use crate::{Game, Round};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SimulationSettings {
+    pub game_count: usize,
+    pub rounds_per_game: usize,
+    pub seed: u64,
+}
+
+// a handful of at least one cube taken from the bag at random, the cubes
+// going back into the bag afterwards
+fn draw_round(cubes: &mut [&str], random: &mut Xorshift) -> Round {
+    let size = 1 + random.up_to(cubes.len() - 1);
+    let mut round = Round {
+        cubes: CubeSet::new(),
+    };
+
+    // the first `size` steps of a Fisher-Yates shuffle
+    for index in 0..size {
+        let other = index + random.up_to(cubes.len() - 1 - index);
+        cubes.swap(index, other);
+
+        round.cubes.add_cubes(cubes[index], 1);
+    }
+
+    round
+}
+
+pub fn simulate_games(bag: &CubeSet, settings: &SimulationSettings) -> Vec<Game> {
+    let mut cubes: Vec<&str> = bag
+        .cubes
+        .iter()
+        .flat_map(|(colour, amount)| iter::repeat_n(colour.as_str(), (*amount).max(0) as usize))
+        .collect();
+
+    if cubes.is_empty() {
+        panic!("Cannot draw cubes from an empty bag");
+    }
+
+    if settings.rounds_per_game == 0 {
+        panic!("Every game needs at least one round");
+    }
+
+    let mut random = Xorshift::new(settings.seed);
+
+    (1..=settings.game_count)
+        .map(|id| Game {
+            id: id as i32,
+            rounds: (0..settings.rounds_per_game)
+                .map(|_| draw_round(&mut cubes, &mut random))
+                .collect(),
+        })
+        .collect()
+}
+
+// one `Game N: ...` line per game, in the puzzle input's format
+pub fn simulate_input(bag: &CubeSet, settings: &SimulationSettings) -> String {
+    let lines: Vec<String> = simulate_games(bag, settings)
+        .iter()
+        .map(Game::to_string)
+        .collect();
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_bag, parse_games};
+
+    fn settings(game_count: usize, rounds_per_game: usize, seed: u64) -> SimulationSettings {
+        SimulationSettings {
+            game_count,
+            rounds_per_game,
+            seed,
+        }
+    }
+
+    fn random_bag(random: &mut Xorshift) -> CubeSet {
+        let colours = ["red", "green", "blue", "gold", "purple"];
+        let mut bag = CubeSet::new();
+
+        for _ in 0..1 + random.up_to(4) {
+            bag.add_cubes(
+                colours[random.up_to(colours.len() - 1)],
+                1 + random.up_to(20) as i32,
+            );
+        }
+
+        bag
+    }
+
+    #[test]
+    fn simulate_games_test() {
+        let bag = parse_bag("red=12,green=13,blue=14").unwrap();
+        let games = simulate_games(&bag, &settings(3, 4, 7));
+
+        assert_eq!(games.len(), 3);
+        assert_eq!(
+            games.iter().map(|game| game.id).collect::<Vec<i32>>(),
+            vec![1, 2, 3]
+        );
+        assert!(games.iter().all(|game| game.rounds.len() == 4));
+        assert_eq!(games, simulate_games(&bag, &settings(3, 4, 7)));
+        assert_ne!(games, simulate_games(&bag, &settings(3, 4, 8)));
+    }
+
+    #[test]
+    fn simulate_games_single_cube_test() {
+        let bag = parse_bag("gold=1").unwrap();
+
+        for game in simulate_games(&bag, &settings(5, 3, 1)) {
+            for round in game.rounds {
+                assert_eq!(round.cubes, bag);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot draw cubes from an empty bag")]
+    fn simulate_games_empty_bag_test() {
+        simulate_games(&CubeSet::new(), &settings(1, 1, 1));
+    }
+
+    #[test]
+    fn simulated_games_are_possible_test() {
+        let mut random = Xorshift::new(2023);
+
+        for seed in 0..200 {
+            let bag = random_bag(&mut random);
+            let settings = settings(1 + seed as usize % 10, 1 + seed as usize % 6, seed);
+            let games = parse_games(&simulate_input(&bag, &settings)).unwrap();
+
+            for game in games.iter() {
+                assert!(game.is_possible_with(&bag), "{game} with {bag:?}");
+                assert!(game.minimum_bag().fits_within(&bag), "{game} with {bag:?}");
+            }
+
+            assert_eq!(games, simulate_games(&bag, &settings));
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use crate::cube_set::CubeSet;
+use crate::{possible_game_ids, Game};
+
+// how the size of a bag is measured
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BagSize {
+    Total,
+    Power,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SmallestBag {
+    pub bag: CubeSet,
+    pub game_ids: Vec<i32>,
+}
+
+struct Search<'a> {
+    // the minimum bag of every game, one count per colour
+    minimum_bags: &'a [Vec<i64>],
+    at_least: usize,
+    size: BagSize,
+    best: Option<(i64, Vec<i64>)>,
+}
+
+fn bag_size(counts: &[i64], size: BagSize) -> i64 {
+    match size {
+        BagSize::Total => counts.iter().sum(),
+        BagSize::Power => counts.iter().product(),
+    }
+}
+
+impl Search<'_> {
+    // picks the count of one colour after the other, trying only counts some
+    // game needs. Once the last colour is reached, its count is whatever the
+    // `at_least`-th game still fitting needs of it
+    fn search(&mut self, colour: usize, counts: &mut Vec<i64>, fitting: &[usize]) {
+        if fitting.len() < self.at_least {
+            return;
+        }
+
+        let colour_count = self.minimum_bags[0].len();
+
+        if colour == colour_count - 1 {
+            let mut needed: Vec<i64> = fitting
+                .iter()
+                .map(|game| self.minimum_bags[*game][colour])
+                .collect();
+            needed.sort_unstable();
+
+            counts.push(needed[self.at_least - 1]);
+            let size = bag_size(counts, self.size);
+
+            if self.best.as_ref().is_none_or(|(best, _)| size < *best) {
+                self.best = Some((size, counts.clone()));
+            }
+
+            counts.pop();
+            return;
+        }
+
+        let mut candidates: Vec<i64> = fitting
+            .iter()
+            .map(|game| self.minimum_bags[*game][colour])
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        for count in candidates {
+            // every other colour needs at least nothing, so a larger total
+            // only grows from here
+            if let (BagSize::Total, Some((best, _))) = (self.size, &self.best) {
+                if counts.iter().sum::<i64>() + count >= *best {
+                    break;
+                }
+            }
+
+            let still_fitting: Vec<usize> = fitting
+                .iter()
+                .copied()
+                .filter(|game| self.minimum_bags[*game][colour] <= count)
+                .collect();
+
+            counts.push(count);
+            self.search(colour + 1, counts, &still_fitting);
+            counts.pop();
+        }
+    }
+}
+
+// the bag with the smallest total or power that makes at least `at_least`
+// of the games possible, or `None` if there are not that many games. The
+// count of every colour but one is tried out, so the work grows with the
+// number of games to the power of the number of colours minus one
+pub fn smallest_bag(games: &[Game], at_least: usize, size: BagSize) -> Option<SmallestBag> {
+    if at_least > games.len() {
+        return None;
+    }
+
+    let minimum_bags: Vec<CubeSet> = games.iter().map(Game::minimum_bag).collect();
+    let colours: Vec<String> = minimum_bags
+        .iter()
+        .fold(CubeSet::new(), |all, minimum_bag| all.max(minimum_bag))
+        .colours()
+        .map(String::from)
+        .collect();
+
+    if at_least == 0 || colours.is_empty() {
+        let bag = CubeSet::new();
+        let game_ids = possible_game_ids(games, &bag);
+
+        return Some(SmallestBag { bag, game_ids });
+    }
+
+    let minimum_bags: Vec<Vec<i64>> = minimum_bags
+        .iter()
+        .map(|minimum_bag| {
+            colours
+                .iter()
+                .map(|colour| minimum_bag.count(colour) as i64)
+                .collect()
+        })
+        .collect();
+
+    let mut search = Search {
+        minimum_bags: &minimum_bags,
+        at_least,
+        size,
+        best: None,
+    };
+
+    let all_games: Vec<usize> = (0..games.len()).collect();
+    search.search(0, &mut Vec::new(), &all_games);
+
+    let (_, counts) = search.best?;
+    let mut bag = CubeSet::new();
+
+    for (colour, count) in colours.iter().zip(counts) {
+        bag.add_cubes(colour, count as i32);
+    }
+
+    let game_ids = possible_game_ids(games, &bag);
+
+    Some(SmallestBag { bag, game_ids })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::{simulate_games, SimulationSettings};
+    use crate::{example_games, parse_games};
+
+    fn size_of(bag: &CubeSet, colours: &[&str], size: BagSize) -> i64 {
+        let counts: Vec<i64> = colours
+            .iter()
+            .map(|colour| bag.count(colour) as i64)
+            .collect();
+
+        bag_size(&counts, size)
+    }
+
+    // the smallest bag over every subset of the games, indexed by how many
+    // games it has to make possible at least
+    fn brute_force(games: &[Game], colours: &[&str], size: BagSize) -> Vec<i64> {
+        let minimum_bags: Vec<CubeSet> = games.iter().map(Game::minimum_bag).collect();
+        let mut best = vec![i64::MAX; games.len() + 1];
+
+        for subset in 0..1_u32 << games.len() {
+            let bag = minimum_bags
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| subset & (1 << index) != 0)
+                .fold(CubeSet::new(), |bag, (_, minimum_bag)| bag.max(minimum_bag));
+
+            let games_in_subset = subset.count_ones() as usize;
+            best[games_in_subset] = best[games_in_subset].min(size_of(&bag, colours, size));
+        }
+
+        for at_least in (0..games.len()).rev() {
+            best[at_least] = best[at_least].min(best[at_least + 1]);
+        }
+
+        best
+    }
+
+    #[test]
+    fn smallest_bag_example_test() {
+        let games = example_games();
+
+        assert_eq!(
+            smallest_bag(&games, 3, BagSize::Total),
+            Some(SmallestBag {
+                bag: CubeSet::from([("blue", 6), ("green", 3), ("red", 6)]),
+                game_ids: vec![1, 2, 5],
+            })
+        );
+        assert_eq!(
+            smallest_bag(&games, 1, BagSize::Power),
+            Some(SmallestBag {
+                bag: CubeSet::from([("blue", 4), ("green", 3), ("red", 1)]),
+                game_ids: vec![2],
+            })
+        );
+        assert_eq!(
+            smallest_bag(&games, 5, BagSize::Total).map(|smallest| smallest.bag),
+            Some(CubeSet::from([("blue", 15), ("green", 13), ("red", 20)]))
+        );
+        assert_eq!(smallest_bag(&games, 6, BagSize::Total), None);
+        assert_eq!(
+            smallest_bag(&games, 0, BagSize::Total),
+            Some(SmallestBag {
+                bag: CubeSet::new(),
+                game_ids: Vec::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn smallest_bag_matches_brute_force_test() {
+        let colours = ["blue", "green", "red"];
+        let bag = CubeSet::from([("blue", 14), ("green", 13), ("red", 12)]);
+        let mut game_sets = vec![example_games()];
+
+        for seed in 0..6 {
+            let settings = SimulationSettings {
+                game_count: 12,
+                rounds_per_game: 1 + seed as usize % 3,
+                seed,
+            };
+
+            game_sets.push(simulate_games(&bag, &settings));
+        }
+
+        for games in game_sets.iter() {
+            for size in [BagSize::Total, BagSize::Power] {
+                let best = brute_force(games, &colours, size);
+
+                for (at_least, best) in best.iter().enumerate().skip(1) {
+                    let smallest = smallest_bag(games, at_least, size).unwrap();
+
+                    assert!(smallest.game_ids.len() >= at_least);
+                    assert_eq!(
+                        size_of(&smallest.bag, &colours, size),
+                        *best,
+                        "at least {at_least} games, {size:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn smallest_bag_input_test() {
+        let games = parse_games(&common::read_input(2)).unwrap();
+
+        for at_least in [1, 10, 50, 90, 100] {
+            for size in [BagSize::Total, BagSize::Power] {
+                let smallest = smallest_bag(&games, at_least, size).unwrap();
+
+                assert!(smallest.game_ids.len() >= at_least);
+                assert_eq!(smallest.game_ids, possible_game_ids(&games, &smallest.bag));
+            }
+        }
+
+        let all_games = games
+            .iter()
+            .fold(CubeSet::new(), |bag, game| bag.max(&game.minimum_bag()));
+
+        assert_eq!(
+            smallest_bag(&games, 100, BagSize::Total).map(|smallest| smallest.bag),
+            Some(all_games)
+        );
+    }
+}
@@ -0,0 +1,465 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::cube_set::CubeSet;
+use crate::{
+    parse_round_cubes, split_game, try_parse_game, Game, GameParseError, LineParseError, Round,
+};
+
+// how the colour has to agree with the amount before it. The puzzle always
+// writes colours in the singular, like "2 red", while English would have
+// "1 red" but "2 reds"
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NumberAgreement {
+    AlwaysSingular,
+    PluralUnlessOne,
+}
+
+// only the listed colours are checked for agreement, their plural being the
+// colour with an "s" added. Any other colour word is taken as it is, so that
+// a colour like "moss" is never mistaken for a plural
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StrictOptions<'a> {
+    pub agreement: NumberAgreement,
+    pub colours: &'a [&'a str],
+    pub reject_zero_amounts: bool,
+}
+
+// lenient parsing adds up a colour listed twice in a round and takes any
+// colour word as it is
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParseMode<'a> {
+    Lenient,
+    Strict(StrictOptions<'a>),
+}
+
+// rounds are numbered from 1
+#[derive(Clone, Debug, PartialEq)]
+pub enum Violation {
+    DuplicateColour {
+        round: usize,
+        colour: String,
+    },
+    Agreement {
+        round: usize,
+        amount: i32,
+        colour: String,
+    },
+    ZeroAmount {
+        round: usize,
+        colour: String,
+    },
+    MalformedRound {
+        round: usize,
+        error: GameParseError,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StrictParseError {
+    Malformed(GameParseError),
+    Violations(Vec<Violation>),
+}
+
+impl StrictOptions<'_> {
+    pub const PUZZLE: StrictOptions<'static> = StrictOptions {
+        agreement: NumberAgreement::AlwaysSingular,
+        colours: &["red", "green", "blue"],
+        reject_zero_amounts: false,
+    };
+}
+
+// the colour in the singular, or `None` if it does not agree with the amount
+fn singular_colour<'a>(
+    amount: i32,
+    colour: &'a str,
+    options: &StrictOptions<'a>,
+) -> Option<&'a str> {
+    if options.colours.contains(&colour) {
+        return match options.agreement {
+            NumberAgreement::PluralUnlessOne if amount != 1 => None,
+            _ => Some(colour),
+        };
+    }
+
+    let singular = options
+        .colours
+        .iter()
+        .find(|singular| colour.strip_suffix('s') == Some(**singular));
+
+    match (singular, options.agreement) {
+        (None, _) => Some(colour),
+        (Some(singular), NumberAgreement::PluralUnlessOne) if amount != 1 => Some(singular),
+        (Some(_), _) => None,
+    }
+}
+
+// every violation in every round is reported, including rounds that cannot be
+// parsed at all
+pub fn parse_game_strict(string: &str, options: &StrictOptions) -> Result<Game, StrictParseError> {
+    let (id, rounds) = split_game(string).map_err(StrictParseError::Malformed)?;
+
+    let mut game = Game {
+        id,
+        rounds: Vec::new(),
+    };
+    let mut violations = Vec::new();
+
+    for (index, round) in rounds.iter().enumerate() {
+        let round_number = index + 1;
+        let mut current_round = Round {
+            cubes: CubeSet::new(),
+        };
+        // a colour with zero cubes is not kept in the round, so the colours
+        // seen so far are tracked apart from it
+        let mut seen_colours: Vec<String> = Vec::new();
+
+        let cubes = match parse_round_cubes(round) {
+            Ok(cubes) => cubes,
+            Err(error) => {
+                violations.push(Violation::MalformedRound {
+                    round: round_number,
+                    error,
+                });
+                continue;
+            }
+        };
+
+        for (amount, colour) in cubes {
+            let colour = match singular_colour(amount, &colour, options) {
+                Some(singular) => singular.to_string(),
+                None => {
+                    violations.push(Violation::Agreement {
+                        round: round_number,
+                        amount,
+                        colour: colour.clone(),
+                    });
+                    colour
+                }
+            };
+
+            if amount == 0 && options.reject_zero_amounts {
+                violations.push(Violation::ZeroAmount {
+                    round: round_number,
+                    colour: colour.clone(),
+                });
+            }
+
+            if seen_colours.contains(&colour) {
+                violations.push(Violation::DuplicateColour {
+                    round: round_number,
+                    colour: colour.clone(),
+                });
+            }
+
+            current_round.cubes.add_cubes(&colour, amount);
+            seen_colours.push(colour);
+        }
+
+        game.rounds.push(current_round);
+    }
+
+    if violations.is_empty() {
+        Ok(game)
+    } else {
+        Err(StrictParseError::Violations(violations))
+    }
+}
+
+pub fn parse_game_with(string: &str, mode: ParseMode) -> Result<Game, StrictParseError> {
+    match mode {
+        ParseMode::Lenient => try_parse_game(string).map_err(StrictParseError::Malformed),
+        ParseMode::Strict(options) => parse_game_strict(string, &options),
+    }
+}
+
+pub fn parse_games_with(
+    input: &str,
+    mode: ParseMode,
+) -> Result<Vec<Game>, Vec<LineParseError<StrictParseError>>> {
+    let mut games = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        match parse_game_with(line, mode) {
+            Ok(game) => games.push(game),
+            Err(error) => errors.push(LineParseError {
+                line_number: index + 1,
+                error,
+            }),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(games)
+    } else {
+        Err(errors)
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::DuplicateColour { round, colour } => {
+                write!(f, "Round {round} lists `{colour}` more than once")
+            }
+            Violation::Agreement {
+                round,
+                amount,
+                colour,
+            } => write!(
+                f,
+                "Round {round} has `{amount} {colour}`, which does not agree in number"
+            ),
+            Violation::ZeroAmount { round, colour } => {
+                write!(f, "Round {round} has zero `{colour}` cubes")
+            }
+            Violation::MalformedRound { round, error } => write!(f, "Round {round}: {error}"),
+        }
+    }
+}
+
+impl fmt::Display for StrictParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StrictParseError::Malformed(error) => write!(f, "{error}"),
+            StrictParseError::Violations(violations) => {
+                let violations: Vec<String> = violations.iter().map(Violation::to_string).collect();
+
+                write!(f, "{}", violations.join("; "))
+            }
+        }
+    }
+}
+
+impl Error for StrictParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_game;
+
+    const ENGLISH: StrictOptions = StrictOptions {
+        agreement: NumberAgreement::PluralUnlessOne,
+        reject_zero_amounts: true,
+        ..StrictOptions::PUZZLE
+    };
+
+    #[test]
+    fn singular_colour_test() {
+        let singular = StrictOptions::PUZZLE;
+
+        assert_eq!(singular_colour(2, "red", &singular), Some("red"));
+        assert_eq!(singular_colour(1, "blues", &singular), None);
+        assert_eq!(singular_colour(1, "blue", &ENGLISH), Some("blue"));
+        assert_eq!(singular_colour(2, "reds", &ENGLISH), Some("red"));
+        assert_eq!(singular_colour(0, "greens", &ENGLISH), Some("green"));
+        assert_eq!(singular_colour(1, "blues", &ENGLISH), None);
+        assert_eq!(singular_colour(2, "red", &ENGLISH), None);
+        assert_eq!(singular_colour(1, "s", &ENGLISH), Some("s"));
+    }
+
+    #[test]
+    fn singular_colour_unlisted_test() {
+        let listed = StrictOptions {
+            colours: &["red", "moss"],
+            ..ENGLISH
+        };
+
+        for options in [StrictOptions::PUZZLE, ENGLISH] {
+            assert_eq!(singular_colour(1, "moss", &options), Some("moss"));
+            assert_eq!(singular_colour(2, "moss", &options), Some("moss"));
+            assert_eq!(singular_colour(2, "golds", &options), Some("golds"));
+        }
+
+        assert_eq!(singular_colour(1, "moss", &listed), Some("moss"));
+        assert_eq!(singular_colour(2, "moss", &listed), None);
+        assert_eq!(singular_colour(2, "mosss", &listed), Some("moss"));
+        assert_eq!(singular_colour(2, "blue", &listed), Some("blue"));
+    }
+
+    #[test]
+    fn parse_game_strict_test() {
+        let line = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+
+        assert_eq!(
+            parse_game_strict(line, &StrictOptions::PUZZLE),
+            Ok(parse_game(line))
+        );
+        assert_eq!(
+            parse_game_strict(
+                "Game 1: 3 blues, 4 reds; 1 red, 2 greens, 6 blues; 2 greens",
+                &ENGLISH
+            ),
+            Ok(parse_game(line))
+        );
+    }
+
+    #[test]
+    fn parse_game_strict_violations_test() {
+        assert_eq!(
+            parse_game_strict(
+                "Game 2: 1 blues, 2 red; 3 green, 1 green, 0 red",
+                &StrictOptions::PUZZLE
+            ),
+            Err(StrictParseError::Violations(vec![
+                Violation::Agreement {
+                    round: 1,
+                    amount: 1,
+                    colour: String::from("blues"),
+                },
+                Violation::DuplicateColour {
+                    round: 2,
+                    colour: String::from("green"),
+                },
+            ]))
+        );
+        assert_eq!(
+            parse_game_strict(
+                "Game 2: 1 blues, 2 red; 3 greens, 1 green, 0 reds",
+                &ENGLISH
+            ),
+            Err(StrictParseError::Violations(vec![
+                Violation::Agreement {
+                    round: 1,
+                    amount: 1,
+                    colour: String::from("blues"),
+                },
+                Violation::Agreement {
+                    round: 1,
+                    amount: 2,
+                    colour: String::from("red"),
+                },
+                Violation::DuplicateColour {
+                    round: 2,
+                    colour: String::from("green"),
+                },
+                Violation::ZeroAmount {
+                    round: 2,
+                    colour: String::from("red"),
+                },
+            ]))
+        );
+        assert_eq!(
+            parse_game_strict("Game 3: 2 red, 2 red", &StrictOptions::PUZZLE),
+            Err(StrictParseError::Violations(vec![
+                Violation::DuplicateColour {
+                    round: 1,
+                    colour: String::from("red"),
+                }
+            ]))
+        );
+        assert_eq!(
+            parse_game_strict(
+                "Game 3: 2 blues; 4 red, 2; 2 red, 2 red",
+                &StrictOptions::PUZZLE
+            ),
+            Err(StrictParseError::Violations(vec![
+                Violation::Agreement {
+                    round: 1,
+                    amount: 2,
+                    colour: String::from("blues"),
+                },
+                Violation::MalformedRound {
+                    round: 2,
+                    error: GameParseError::OddTokenCount(String::from("4 red, 2")),
+                },
+                Violation::DuplicateColour {
+                    round: 3,
+                    colour: String::from("red"),
+                },
+            ]))
+        );
+        assert_eq!(
+            parse_game_strict("Game 3 2 red", &StrictOptions::PUZZLE),
+            Err(StrictParseError::Malformed(GameParseError::MissingColon(
+                String::from("Game 3 2 red")
+            )))
+        );
+    }
+
+    #[test]
+    fn parse_game_strict_zero_duplicate_test() {
+        assert_eq!(
+            parse_game_strict(
+                "Game 6: 0 red, 1 red; 2 blue, 0 blue",
+                &StrictOptions::PUZZLE
+            ),
+            Err(StrictParseError::Violations(vec![
+                Violation::DuplicateColour {
+                    round: 1,
+                    colour: String::from("red"),
+                },
+                Violation::DuplicateColour {
+                    round: 2,
+                    colour: String::from("blue"),
+                },
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_game_with_test() {
+        let line = "Game 4: 1 green, 2 green; 0 red";
+
+        assert_eq!(
+            parse_game_with(line, ParseMode::Lenient).map(|game| game.rounds[0].green_cubes()),
+            Ok(3)
+        );
+        assert_eq!(
+            parse_game_with(
+                line,
+                ParseMode::Strict(StrictOptions {
+                    reject_zero_amounts: true,
+                    ..StrictOptions::PUZZLE
+                })
+            )
+            .unwrap_err()
+            .to_string(),
+            "Round 1 lists `green` more than once; Round 2 has zero `red` cubes"
+        );
+        assert_eq!(
+            parse_game_with("Game 5: 1 red; 2", ParseMode::Strict(StrictOptions::PUZZLE))
+                .unwrap_err()
+                .to_string(),
+            "Round 2: Round is not of even length: `2`"
+        );
+    }
+
+    #[test]
+    fn parse_games_with_test() {
+        let input = "Game 1: 1 blue\nGame 2: 2 blues\nGame 3: 1 red, 1 red";
+
+        assert_eq!(
+            parse_games_with(input, ParseMode::Lenient).map(|games| games.len()),
+            Ok(3)
+        );
+
+        let errors = parse_games_with(input, ParseMode::Strict(StrictOptions::PUZZLE)).unwrap_err();
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+            vec![
+                "Line 2: Round 1 has `2 blues`, which does not agree in number",
+                "Line 3: Round 1 lists `red` more than once"
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_games_with_input_test() {
+        let input = common::read_input(2);
+        let strict = ParseMode::Strict(StrictOptions {
+            reject_zero_amounts: true,
+            ..StrictOptions::PUZZLE
+        });
+
+        assert_eq!(
+            parse_games_with(&input, strict),
+            parse_games_with(&input, ParseMode::Lenient)
+        );
+    }
+}
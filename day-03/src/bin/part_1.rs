@@ -98,6 +98,7 @@ mod tests {
     use crate::{is_number_adjacent_to_symbol, part_1};
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn is_number_adjacent_to_symbol_test() {
         let number_regex = Regex::new(r"\d+").unwrap();
         let symbol_regex = Regex::new(r"[^0-9.\s]").unwrap();
@@ -73,18 +73,18 @@ fn part_2() -> u32 {
         let mut winning_numbers: u32 = 0;
 
         for number in game.numbers.iter() {
-            if game.winning_numbers.contains(&number) {
+            if game.winning_numbers.contains(number) {
                 winning_numbers += 1;
             }
         }
 
         let copies_for_current_index = *copies.get(&index).unwrap_or(&0);
-        total_cards += 1 + 1 * copies_for_current_index;
+        total_cards += 1 + copies_for_current_index;
 
         if winning_numbers > 0 {
             for future_index in index + 1..=index + winning_numbers as usize {
                 let current = copies.get(&future_index).unwrap_or(&0);
-                let next = current + 1 + (1 * copies_for_current_index);
+                let next = current + 1 + copies_for_current_index;
 
                 copies.insert(future_index, next);
             }
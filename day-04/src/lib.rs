@@ -56,6 +56,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn is_card_line_test() {
         let game_line_1 = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
         let game_line_2 = "Card    2: 13 32 20    16 61 | 61 30 68 82 17    32 24 19";